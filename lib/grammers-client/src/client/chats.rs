@@ -10,9 +10,12 @@
 
 use super::Client;
 use crate::types::{
-    chats::AdminRightsBuilderInner, chats::BannedRightsBuilderInner, AdminRightsBuilder,
-    BannedRightsBuilder, Chat, ChatMap, IterBuffer, Message, Participant, Photo, User,
+    chats::AdminRightsBuilderInner, chats::BannedRightsBuilderInner, AdminLogEvent, AdminLogFilter,
+    AdminRightsBuilder, BannedRightsBuilder, Chat, ChatMap, InviteLink, InviteLinkOptions,
+    IterBuffer, Message, Participant, Photo, Uploaded, User,
 };
+use crate::utils::generate_random_id;
+use chrono::{DateTime, Utc};
 use grammers_mtproto::mtp::RpcError;
 pub use grammers_mtsender::{AuthorizationError, InvocationError};
 use grammers_session::{PackedChat, PackedType};
@@ -24,6 +27,9 @@ use std::time::Duration;
 
 const MAX_PARTICIPANT_LIMIT: usize = 200;
 const MAX_PHOTO_LIMIT: usize = 100;
+const MAX_BLOCKED_LIMIT: usize = 100;
+const MAX_INVITE_LINK_LIMIT: usize = 100;
+const MAX_ADMIN_LOG_LIMIT: usize = 100;
 const KICK_BAN_DURATION: i32 = 60; // in seconds, in case the second request fails
 
 pub enum ParticipantIter {
@@ -340,11 +346,332 @@ impl ProfilePhotoIter {
     }
 }
 
+pub type BlockedUserIter = IterBuffer<tl::functions::contacts::GetBlocked, User>;
+
+impl BlockedUserIter {
+    fn new(client: &Client) -> Self {
+        Self::from_request(
+            client,
+            MAX_BLOCKED_LIMIT,
+            tl::functions::contacts::GetBlocked {
+                my_stories_from: false,
+                offset: 0,
+                limit: 0,
+            },
+        )
+    }
+
+    /// Determines how many users are blocked in total.
+    ///
+    /// This only performs a network call if `next` has not been called before.
+    pub async fn total(&mut self) -> Result<usize, InvocationError> {
+        if let Some(total) = self.total {
+            return Ok(total);
+        }
+
+        self.request.limit = 1;
+        let total = match self.client.invoke(&self.request).await? {
+            tl::enums::contacts::Blocked::Blocked(blocked) => blocked.blocked.len(),
+            tl::enums::contacts::Blocked::Slice(blocked) => blocked.count as usize,
+        };
+        self.total = Some(total);
+        Ok(total)
+    }
+
+    /// Return the next blocked user from the internal buffer, filling the buffer previously if
+    /// it's empty.
+    ///
+    /// Blocked peers which are not users are skipped.
+    ///
+    /// Returns `None` if the `limit` is reached or there are no blocked users left.
+    pub async fn next(&mut self) -> Result<Option<User>, InvocationError> {
+        if let Some(result) = self.next_raw() {
+            return result;
+        }
+
+        self.request.limit = self.determine_limit(MAX_BLOCKED_LIMIT);
+        let (blocked, users, chats) = match self.client.invoke(&self.request).await? {
+            tl::enums::contacts::Blocked::Blocked(blocked) => {
+                self.last_chunk = true;
+                self.total = Some(blocked.blocked.len());
+                (blocked.blocked, blocked.users, blocked.chats)
+            }
+            tl::enums::contacts::Blocked::Slice(blocked) => {
+                self.last_chunk = blocked.blocked.len() < self.request.limit as usize;
+                self.total = Some(blocked.count as usize);
+                (blocked.blocked, blocked.users, blocked.chats)
+            }
+        };
+
+        {
+            let mut state = self.client.0.state.write().unwrap();
+            // Telegram can return peers without hash (e.g. Users with 'min: true')
+            let _ = state.chat_hashes.extend(&users, &chats);
+        }
+
+        self.request.offset += blocked.len() as i32;
+
+        let chats = ChatMap::new(users, chats);
+        self.buffer.extend(blocked_users(blocked, &chats));
+
+        Ok(self.pop_item())
+    }
+}
+
+pub type InviteLinkIter = IterBuffer<tl::functions::messages::GetExportedChatInvites, InviteLink>;
+
+impl InviteLinkIter {
+    fn new(client: &Client, chat: PackedChat, revoked: bool) -> Self {
+        Self::from_request(
+            client,
+            MAX_INVITE_LINK_LIMIT,
+            tl::functions::messages::GetExportedChatInvites {
+                revoked,
+                peer: chat.to_input_peer(),
+                admin_id: tl::enums::InputUser::UserSelf,
+                offset_date: None,
+                offset_link: None,
+                limit: 0,
+            },
+        )
+    }
+
+    /// Determines how many invite links there are in total.
+    ///
+    /// This only performs a network call if `next` has not been called before.
+    pub async fn total(&mut self) -> Result<usize, InvocationError> {
+        if let Some(total) = self.total {
+            return Ok(total);
+        }
+
+        self.request.limit = 1;
+        let tl::enums::messages::ExportedChatInvites::Invites(invites) =
+            self.client.invoke(&self.request).await?;
+        let total = invites.count as usize;
+        self.total = Some(total);
+        Ok(total)
+    }
+
+    /// Return the next invite link from the internal buffer, filling the buffer previously if
+    /// it's empty.
+    ///
+    /// Returns `None` if the `limit` is reached or there are no invite links left.
+    pub async fn next(&mut self) -> Result<Option<InviteLink>, InvocationError> {
+        if let Some(result) = self.next_raw() {
+            return result;
+        }
+
+        self.request.limit = self.determine_limit(MAX_INVITE_LINK_LIMIT);
+        let tl::enums::messages::ExportedChatInvites::Invites(invites) =
+            self.client.invoke(&self.request).await?;
+
+        self.last_chunk = invites.invites.len() < self.request.limit as usize;
+        self.total = Some(invites.count as usize);
+
+        {
+            let mut state = self.client.0.state.write().unwrap();
+            // Telegram can return peers without hash (e.g. Users with 'min: true')
+            let _ = state.chat_hashes.extend(&invites.users, &[]);
+        }
+
+        match last_exported_invite(&invites.invites) {
+            Some(last) => {
+                self.request.offset_date = Some(last.date);
+                self.request.offset_link = Some(last.link.clone());
+            }
+            None => self.last_chunk = true,
+        }
+
+        let chats = ChatMap::new(invites.users, Vec::new());
+        self.buffer.extend(
+            invites
+                .invites
+                .into_iter()
+                .filter_map(|invite| InviteLink::from_raw(invite, &chats)),
+        );
+
+        Ok(self.pop_item())
+    }
+}
+
+pub type AdminLogIter = IterBuffer<tl::functions::channels::GetAdminLog, AdminLogEvent>;
+
+impl AdminLogIter {
+    fn new(client: &Client, chat: PackedChat) -> Self {
+        Self::from_request(
+            client,
+            MAX_ADMIN_LOG_LIMIT,
+            tl::functions::channels::GetAdminLog {
+                // Small group chats have no admin log, so let Telegram report the error.
+                channel: chat
+                    .try_to_input_channel()
+                    .unwrap_or(tl::enums::InputChannel::Empty),
+                q: String::new(),
+                events_filter: None,
+                admins: None,
+                max_id: 0,
+                min_id: 0,
+                limit: 0,
+            },
+        )
+    }
+
+    /// Return the next event from the internal buffer, filling the buffer previously if it's
+    /// empty.
+    ///
+    /// Events are returned from newest to oldest.
+    ///
+    /// Returns `None` if the `limit` is reached or there are no events left.
+    pub async fn next(&mut self) -> Result<Option<AdminLogEvent>, InvocationError> {
+        if let Some(result) = self.next_raw() {
+            return result;
+        }
+
+        self.request.limit = self.determine_limit(MAX_ADMIN_LOG_LIMIT);
+        let tl::enums::channels::AdminLogResults::Results(results) =
+            self.client.invoke(&self.request).await?;
+
+        self.last_chunk = results.events.len() < self.request.limit as usize;
+
+        {
+            let mut state = self.client.0.state.write().unwrap();
+            // Telegram can return peers without hash (e.g. Users with 'min: true')
+            let _ = state.chat_hashes.extend(&results.users, &results.chats);
+        }
+
+        if let Some(tl::enums::ChannelAdminLogEvent::Event(last)) = results.events.last() {
+            self.request.max_id = last.id;
+        }
+
+        let chats = ChatMap::new(results.users, results.chats);
+        let client = &self.client;
+        self.buffer.extend(
+            results
+                .events
+                .into_iter()
+                .map(|event| AdminLogEvent::new(client, event, &chats)),
+        );
+
+        Ok(self.pop_item())
+    }
+
+    /// Only return events of the kinds enabled in the filter.
+    pub fn filter(mut self, filter: AdminLogFilter) -> Self {
+        self.request.events_filter = filter.into_raw();
+        self
+    }
+
+    /// Only return events which contain the given text, such as in edited messages.
+    pub fn query(mut self, query: &str) -> Self {
+        self.request.q = query.to_string();
+        self
+    }
+}
+
+/// The users among the blocked peers, dropping those which are unknown or not users.
+fn blocked_users(blocked: Vec<tl::enums::PeerBlocked>, chats: &ChatMap) -> Vec<User> {
+    blocked
+        .into_iter()
+        .filter_map(
+            |tl::enums::PeerBlocked::Blocked(blocked)| match blocked.peer_id {
+                tl::enums::Peer::User(user) => chats.get_user(user.user_id).cloned(),
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// The last invite which is an actual link, skipping pseudo-links such as public join requests.
+fn last_exported_invite(
+    invites: &[tl::enums::ExportedChatInvite],
+) -> Option<&tl::types::ChatInviteExported> {
+    invites.iter().rev().find_map(|invite| match invite {
+        tl::enums::ExportedChatInvite::ChatInviteExported(invite) => Some(invite),
+        tl::enums::ExportedChatInvite::ChatInvitePublicJoinRequests => None,
+    })
+}
+
+/// The users and chats included in the updates, if any.
+fn updates_peers(updates: tl::enums::Updates) -> (Vec<tl::enums::User>, Vec<tl::enums::Chat>) {
+    match updates {
+        tl::enums::Updates::Combined(updates) => (updates.users, updates.chats),
+        tl::enums::Updates::Updates(updates) => (updates.users, updates.chats),
+        _ => (Vec::new(), Vec::new()),
+    }
+}
+
+/// The chat the peer resolved by a username or phone number refers to.
+fn resolved_chat(
+    peer: tl::enums::Peer,
+    users: Vec<tl::enums::User>,
+    chats: Vec<tl::enums::Chat>,
+) -> Option<Chat> {
+    match peer {
+        tl::enums::Peer::User(tl::types::PeerUser { user_id }) => users
+            .into_iter()
+            .map(Chat::from_user)
+            .find(|chat| chat.id() == user_id),
+        tl::enums::Peer::Chat(tl::types::PeerChat { chat_id })
+        | tl::enums::Peer::Channel(tl::types::PeerChannel {
+            channel_id: chat_id,
+        }) => chats
+            .into_iter()
+            .map(Chat::from_chat)
+            .find(|chat| chat.id() == chat_id),
+    }
+}
+
+/// Phone numbers are often written with separators Telegram does not understand.
+fn normalize_phone(phone: &str) -> String {
+    phone.chars().filter(|c| !matches!(c, ' ' | '-')).collect()
+}
+
+/// The user imported as the contact with the given client identifier, if any.
+fn imported_user(
+    imported: tl::types::contacts::ImportedContacts,
+    client_id: i64,
+) -> Option<tl::enums::User> {
+    let user_id = imported.imported.iter().find_map(|contact| {
+        let tl::enums::ImportedContact::Contact(contact) = contact;
+        (contact.client_id == client_id).then_some(contact.user_id)
+    })?;
+
+    imported.users.into_iter().find(|user| match user {
+        tl::enums::User::Empty(user) => user.id == user_id,
+        tl::enums::User::User(user) => user.id == user_id,
+    })
+}
+
+/// The hash Telegram expects to find out whether the contact list changed.
+///
+/// It is computed from the amount of saved contacts followed by the sorted contact identifiers.
+fn contacts_hash(contacts: &tl::types::contacts::Contacts) -> i64 {
+    let mut ids = contacts
+        .contacts
+        .iter()
+        .map(|tl::enums::Contact::Contact(contact)| contact.user_id)
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+
+    std::iter::once(contacts.saved_count as i64)
+        .chain(ids)
+        .fold(0u64, |hash, id| {
+            let hash = hash ^ (hash >> 21);
+            let hash = hash ^ (hash << 35);
+            let hash = hash ^ (hash >> 4);
+            hash.wrapping_add(id as u64)
+        }) as i64
+}
+
 /// Method implementations related to dealing with chats or other users.
 impl Client {
     /// Resolves a username into the chat that owns it, if any.
     ///
+    /// The username may start with `@`, which is ignored.
+    ///
     /// Note that this method is expensive to call, and can quickly cause long flood waits.
+    /// The access hash of the resolved chat is cached, so it's better to keep the [`PackedChat`]
+    /// around than resolving the same username multiple times.
     ///
     /// # Examples
     ///
@@ -359,7 +686,7 @@ impl Client {
     pub async fn resolve_username(&self, username: &str) -> Result<Option<Chat>, InvocationError> {
         let tl::types::contacts::ResolvedPeer { peer, users, chats } = match self
             .invoke(&tl::functions::contacts::ResolveUsername {
-                username: username.into(),
+                username: username.strip_prefix('@').unwrap_or(username).into(),
             })
             .await
         {
@@ -374,21 +701,333 @@ impl Client {
             let _ = state.chat_hashes.extend(&users, &chats);
         }
 
-        Ok(match peer {
-            tl::enums::Peer::User(tl::types::PeerUser { user_id }) => users
-                .into_iter()
-                .map(Chat::from_user)
-                .find(|chat| chat.id() == user_id),
-            tl::enums::Peer::Chat(tl::types::PeerChat { chat_id })
-            | tl::enums::Peer::Channel(tl::types::PeerChannel {
-                channel_id: chat_id,
-            }) => chats
-                .into_iter()
-                .map(Chat::from_chat)
-                .find(|chat| chat.id() == chat_id),
+        Ok(resolved_chat(peer, users, chats))
+    }
+
+    /// Resolves a phone number into the user that owns it, if any.
+    ///
+    /// Spaces and dashes in the phone number are ignored, but otherwise it should be in
+    /// international format, such as `+34 600-000-000`.
+    ///
+    /// Note that users can hide their phone number from everyone, in which case they won't be
+    /// found, and that this method can quickly cause long flood waits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(user) = client.resolve_phone("+34 600-000-000").await? {
+    ///     println!("Found user!: {:?}", user.full_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_phone(&self, phone: &str) -> Result<Option<User>, InvocationError> {
+        let tl::types::contacts::ResolvedPeer { peer, users, chats } = match self
+            .invoke(&tl::functions::contacts::ResolvePhone {
+                phone: normalize_phone(phone),
+            })
+            .await
+        {
+            Ok(tl::enums::contacts::ResolvedPeer::Peer(p)) => p,
+            Err(err) if err.is("PHONE_NOT_OCCUPIED") => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        {
+            let mut state = self.0.state.write().unwrap();
+            // Telegram can return peers without hash (e.g. Users with 'min: true')
+            let _ = state.chat_hashes.extend(&users, &chats);
+        }
+
+        Ok(match resolved_chat(peer, users, chats) {
+            Some(Chat::User(user)) => Some(user),
+            _ => None,
         })
     }
 
+    async fn import_contact(
+        &self,
+        phone: &str,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Option<User>, InvocationError> {
+        let client_id = generate_random_id();
+        let tl::enums::contacts::ImportedContacts::Contacts(imported) = self
+            .invoke(&tl::functions::contacts::ImportContacts {
+                contacts: vec![tl::types::InputPhoneContact {
+                    client_id,
+                    phone: phone.to_string(),
+                    first_name: first_name.to_string(),
+                    last_name: last_name.to_string(),
+                }
+                .into()],
+            })
+            .await?;
+
+        let user = match imported_user(imported, client_id) {
+            Some(user) => user,
+            None => return Ok(None),
+        };
+
+        {
+            let mut state = self.0.state.write().unwrap();
+            let _ = state.chat_hashes.extend(std::slice::from_ref(&user), &[]);
+        }
+
+        Ok(Some(User::from_raw(user)))
+    }
+
+    /// Fetch all the users saved as contacts of the logged-in user.
+    ///
+    /// The list is remembered for as long as the client runs. If the contacts did not change
+    /// since the last call, Telegram won't send them again, and the remembered list is returned
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// for user in client.get_contacts().await? {
+    ///     println!("{} is a contact", user.full_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_contacts(&self) -> Result<Vec<User>, InvocationError> {
+        let cached = self.0.state.read().unwrap().contacts.clone();
+        let hash = cached.as_ref().map_or(0, |(hash, _)| *hash);
+
+        let users = match self
+            .invoke(&tl::functions::contacts::GetContacts { hash })
+            .await?
+        {
+            tl::enums::contacts::Contacts::Contacts(contacts) => {
+                let hash = contacts_hash(&contacts);
+                let mut state = self.0.state.write().unwrap();
+                let _ = state.chat_hashes.extend(&contacts.users, &[]);
+                state.contacts = Some((hash, contacts.users.clone()));
+                contacts.users
+            }
+            tl::enums::contacts::Contacts::NotModified => {
+                cached.map(|(_, users)| users).unwrap_or_default()
+            }
+        };
+
+        Ok(users.into_iter().map(User::from_raw).collect())
+    }
+
+    /// Save the owner of a phone number as a contact of the logged-in user.
+    ///
+    /// Returns the user who owns the phone number, or `None` if it does not belong to anyone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(user) = client.add_contact("+34 600-000-000", "Alice", "").await? {
+    ///     println!("Added {} as a contact", user.full_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn add_contact(
+        &self,
+        phone: &str,
+        first_name: &str,
+        last_name: &str,
+    ) -> Result<Option<User>, InvocationError> {
+        self.import_contact(&normalize_phone(phone), first_name, last_name)
+            .await
+    }
+
+    /// Remove the given users from the contacts of the logged-in user.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.delete_contacts(&[&user]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_contacts(&self, users: &[&User]) -> Result<(), InvocationError> {
+        self.invoke(&tl::functions::contacts::DeleteContacts {
+            id: users
+                .iter()
+                .map(|user| user.pack().to_input_user_lossy())
+                .collect(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Block a user, so that they can no longer message the logged-in user.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.block_user(&user).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn block_user<C: Into<PackedChat>>(&self, user: C) -> Result<(), InvocationError> {
+        self.invoke(&tl::functions::contacts::Block {
+            my_stories_from: false,
+            id: user.into().to_input_peer(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Unblock a user that was previously blocked with [`Client::block_user`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.unblock_user(&user).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn unblock_user<C: Into<PackedChat>>(&self, user: C) -> Result<(), InvocationError> {
+        self.invoke(&tl::functions::contacts::Unblock {
+            my_stories_from: false,
+            id: user.into().to_input_peer(),
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Iterate over the users blocked by the logged-in user.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut blocked = client.iter_blocked_users();
+    ///
+    /// while let Some(user) = blocked.next().await? {
+    ///     println!("{} is blocked", user.full_name());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_blocked_users(&self) -> BlockedUserIter {
+        BlockedUserIter::new(self)
+    }
+
+    /// Iterate over the log of administrative actions taken in a group or channel, from the
+    /// most recent to the oldest.
+    ///
+    /// Only administrators can see the log, which keeps the actions of the last 48 hours.
+    /// Small group chats have no log, so Telegram will return an error for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use grammers_client::types::{AdminLogAction, AdminLogFilter};
+    ///
+    /// let mut events = client
+    ///     .iter_admin_log(&chat)
+    ///     .filter(AdminLogFilter::new().delete(true))
+    ///     .limit(50);
+    ///
+    /// while let Some(event) = events.next().await? {
+    ///     if let AdminLogAction::DeleteMessage(message) = event.action() {
+    ///         println!("{} deleted: {}", event.actor_id(), message.text());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_admin_log<C: Into<PackedChat>>(&self, chat: C) -> AdminLogIter {
+        AdminLogIter::new(self, chat.into())
+    }
+
+    /// Iterate over the invite links created by the logged-in user in a group or channel.
+    ///
+    /// If `revoked` is `true`, only the links which were revoked are returned. Otherwise, only
+    /// the links which can still be used are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut links = client.iter_invite_links(&chat, false);
+    ///
+    /// while let Some(link) = links.next().await? {
+    ///     println!("{} was used {} times", link.link(), link.usage_count());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_invite_links<C: Into<PackedChat>>(&self, chat: C, revoked: bool) -> InviteLinkIter {
+        InviteLinkIter::new(self, chat.into(), revoked)
+    }
+
+    /// Create a new invite link to a group or channel.
+    ///
+    /// Returns `None` if Telegram did not export an actual link (for example, only the
+    /// pseudo-link used to review join requests to a public chat).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use grammers_client::types::InviteLinkOptions;
+    ///
+    /// if let Some(link) = client
+    ///     .create_invite_link(&chat, InviteLinkOptions::new().usage_limit(1))
+    ///     .await?
+    /// {
+    ///     println!("Single-use link: {}", link.link());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_invite_link<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        options: InviteLinkOptions,
+    ) -> Result<Option<InviteLink>, InvocationError> {
+        let invite = self
+            .invoke(&options.into_request(chat.into().to_input_peer()))
+            .await?;
+
+        Ok(InviteLink::from_raw(invite, &ChatMap::empty()))
+    }
+
+    /// Revoke an invite link to a group or channel, so that it can no longer be used to join.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.revoke_invite_link(&chat, "https://t.me/+abc").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn revoke_invite_link<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        link: &str,
+    ) -> Result<(), InvocationError> {
+        self.invoke(&tl::functions::messages::EditExportedChatInvite {
+            revoked: true,
+            peer: chat.into().to_input_peer(),
+            link: link.to_string(),
+            expire_date: None,
+            usage_limit: None,
+            request_needed: None,
+            title: None,
+        })
+        .await?;
+        Ok(())
+    }
+
     /// Fetch full information about the currently logged-in user.
     ///
     /// Although this method is cheap to call, you might want to cache the results somewhere.
@@ -429,7 +1068,7 @@ impl Client {
     /// let mut participants = client.iter_participants(&chat);
     ///
     /// while let Some(participant) = participants.next().await? {
-    ///     println!("{} has role {:?}", participant.user.first_name(), participant.role);
+    ///     println!("{} has role {:?}", participant.user().first_name(), participant.role());
     /// }
     /// # Ok(())
     /// # }
@@ -495,6 +1134,37 @@ impl Client {
         }
     }
 
+    /// Bans the participant from the chat, until the given date or forever.
+    ///
+    /// This will fail if you do not have sufficient permissions to perform said operation.
+    ///
+    /// Banned users are removed from the chat and cannot join again until the ban expires or
+    /// is lifted with [`Client::set_banned_rights`]. Small group chats have no concept of bans,
+    /// so the user is only removed from them, and `until` is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use chrono::{Duration, Utc};
+    ///
+    /// // Keep them out for a day.
+    /// client.ban_participant(&chat, &user, Some(Utc::now() + Duration::days(1))).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ban_participant<C: Into<PackedChat>, U: Into<PackedChat>>(
+        &self,
+        chat: C,
+        user: U,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<(), InvocationError> {
+        self.set_banned_rights(chat, user)
+            .view_messages(false)
+            .until(until.map(|date| date.timestamp() as i32).unwrap_or(0))
+            .await
+    }
+
     /// Set the banned rights for a specific user.
     ///
     /// Returns a new [`BannedRightsBuilder`] instance. Check out the documentation for that type
@@ -585,6 +1255,27 @@ impl Client {
         )
     }
 
+    /// Removes all the administrator rights of the user in the chat.
+    ///
+    /// This is the same as awaiting [`Client::set_admin_rights`] without granting any rights,
+    /// and will fail if you do not have sufficient permissions to perform said operation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.demote_admin(&chat, &user).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn demote_admin<C: Into<PackedChat>, U: Into<PackedChat>>(
+        &self,
+        chat: C,
+        user: U,
+    ) -> Result<(), InvocationError> {
+        self.set_admin_rights(chat, user).await
+    }
+
     /// Iterate over the history of profile photos for the given user or chat.
     ///
     /// Note that the current photo might not be present in the history, and to avoid doing more
@@ -610,6 +1301,59 @@ impl Client {
         ProfilePhotoIter::new(self, chat.into())
     }
 
+    /// Uploads a new profile photo for the logged-in user, and returns it.
+    ///
+    /// The photo must have been uploaded first with [`Client::upload_file`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let uploaded = client.upload_file("/home/username/photos/me.jpg").await?;
+    /// let photo = client.set_profile_photo(uploaded).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_profile_photo(&self, photo: Uploaded) -> Result<Photo, InvocationError> {
+        let tl::enums::photos::Photo::Photo(photo) = self
+            .invoke(&tl::functions::photos::UploadProfilePhoto {
+                fallback: false,
+                bot: None,
+                file: Some(photo.input_file),
+                video: None,
+                video_start_ts: None,
+                video_emoji_markup: None,
+            })
+            .await?;
+
+        Ok(Photo::from_raw(photo.photo, self.clone()))
+    }
+
+    /// Deletes one of the profile photos of the logged-in user.
+    ///
+    /// Returns `true` if the photo was deleted, or `false` if it was not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let me = client.get_me().await?;
+    /// if let Some(photo) = client.iter_profile_photos(&me).next().await? {
+    ///     client.delete_profile_photo(&photo).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn delete_profile_photo(&self, photo: &Photo) -> Result<bool, InvocationError> {
+        let deleted = self
+            .invoke(&tl::functions::photos::DeletePhotos {
+                id: vec![photo.to_input_photo()],
+            })
+            .await?;
+
+        Ok(deleted.contains(&photo.id()))
+    }
+
     /// Convert a [`PackedChat`] back into a [`Chat`].
     ///
     /// # Example
@@ -852,6 +1596,82 @@ impl Client {
             Some(_) => Ok(None),
         }
     }
+
+    /// Creates a new small group chat with the given title and initial members.
+    ///
+    /// Telegram requires at least one other user to create a small group. The created chat is
+    /// returned, unless Telegram did not include it in its response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(chat) = client.create_group("Book club", &[user.pack()]).await? {
+    ///     client.send_message(&chat, "Welcome!").await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_group(
+        &self,
+        title: &str,
+        users: &[PackedChat],
+    ) -> Result<Option<Chat>, InvocationError> {
+        let tl::enums::messages::InvitedUsers::Users(invited) = self
+            .invoke(&tl::functions::messages::CreateChat {
+                users: users.iter().map(PackedChat::to_input_user_lossy).collect(),
+                title: title.to_string(),
+                ttl_period: None,
+            })
+            .await?;
+
+        Ok(self.created_chat(invited.updates))
+    }
+
+    /// Creates a new supergroup with the given title and about text.
+    ///
+    /// The created chat is returned, unless Telegram did not include it in its response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let chat = client.create_supergroup("Book club", "We read books").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_supergroup(
+        &self,
+        title: &str,
+        about: &str,
+    ) -> Result<Option<Chat>, InvocationError> {
+        let updates = self
+            .invoke(&tl::functions::channels::CreateChannel {
+                broadcast: false,
+                megagroup: true,
+                for_import: false,
+                forum: false,
+                title: title.to_string(),
+                about: about.to_string(),
+                geo_point: None,
+                address: None,
+                ttl_period: None,
+            })
+            .await?;
+
+        Ok(self.created_chat(updates))
+    }
+
+    /// Caches the peers in the updates sent after creating a chat, and returns the chat.
+    fn created_chat(&self, updates: tl::enums::Updates) -> Option<Chat> {
+        let (users, chats) = updates_peers(updates);
+        {
+            let mut state = self.0.state.write().unwrap();
+            // Telegram can return peers without hash (e.g. Users with 'min: true')
+            let _ = state.chat_hashes.extend(&users, &chats);
+        }
+        chats.into_iter().next().map(Chat::from_chat)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -919,3 +1739,179 @@ impl ParticipantPermissions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn created_chat_is_taken_from_updates() {
+        let chat: tl::enums::Chat = tl::types::ChatForbidden {
+            id: 123,
+            title: "Book club".into(),
+        }
+        .into();
+        let updates = tl::types::Updates {
+            updates: Vec::new(),
+            users: Vec::new(),
+            chats: vec![chat.clone()],
+            date: 0,
+            seq: 0,
+        };
+
+        let (users, chats) = updates_peers(updates.into());
+        assert!(users.is_empty());
+        assert_eq!(chats, vec![chat]);
+
+        let (users, chats) = updates_peers(tl::enums::Updates::TooLong);
+        assert!(users.is_empty());
+        assert!(chats.is_empty());
+    }
+
+    #[test]
+    fn phone_separators_are_removed() {
+        assert_eq!(normalize_phone("+34 600-000-000"), "+34600000000");
+        assert_eq!(normalize_phone("+34600000000"), "+34600000000");
+    }
+
+    #[test]
+    fn imported_user_matches_client_id() {
+        let imported = |client_id| tl::types::contacts::ImportedContacts {
+            imported: vec![tl::types::ImportedContact {
+                user_id: 2,
+                client_id,
+            }
+            .into()],
+            popular_invites: Vec::new(),
+            retry_contacts: Vec::new(),
+            users: vec![
+                tl::types::UserEmpty { id: 1 }.into(),
+                tl::types::UserEmpty { id: 2 }.into(),
+            ],
+        };
+
+        assert_eq!(
+            imported_user(imported(7), 7),
+            Some(tl::types::UserEmpty { id: 2 }.into())
+        );
+        assert_eq!(imported_user(imported(8), 7), None);
+    }
+
+    #[test]
+    fn contacts_hash_uses_sorted_ids() {
+        let contacts = |ids: &[i64]| tl::types::contacts::Contacts {
+            contacts: ids
+                .iter()
+                .map(|&user_id| {
+                    tl::types::Contact {
+                        user_id,
+                        mutual: false,
+                    }
+                    .into()
+                })
+                .collect(),
+            saved_count: 0,
+            users: Vec::new(),
+        };
+
+        assert_eq!(contacts_hash(&contacts(&[])), 0);
+        assert_eq!(contacts_hash(&contacts(&[5])), 5);
+        assert_eq!(
+            contacts_hash(&contacts(&[1, 2])),
+            contacts_hash(&contacts(&[2, 1]))
+        );
+        assert_ne!(
+            contacts_hash(&contacts(&[1, 2])),
+            contacts_hash(&contacts(&[1, 3]))
+        );
+    }
+
+    #[test]
+    fn blocked_users_skips_other_peers() {
+        let chats = ChatMap::new(
+            vec![
+                tl::types::UserEmpty { id: 1 }.into(),
+                tl::types::UserEmpty { id: 2 }.into(),
+            ],
+            Vec::new(),
+        );
+        let blocked = |peer_id: tl::enums::Peer| tl::types::PeerBlocked { peer_id, date: 0 }.into();
+
+        let users = blocked_users(
+            vec![
+                blocked(tl::types::PeerUser { user_id: 2 }.into()),
+                blocked(tl::types::PeerChannel { channel_id: 1 }.into()),
+                blocked(tl::types::PeerUser { user_id: 3 }.into()),
+            ],
+            &chats,
+        );
+        assert_eq!(users.iter().map(|u| u.id()).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn last_exported_invite_skips_pseudo_links() {
+        let invite = |link: &str| {
+            tl::types::ChatInviteExported {
+                revoked: false,
+                permanent: false,
+                request_needed: false,
+                link: link.to_string(),
+                admin_id: 1,
+                date: 0,
+                start_date: None,
+                expire_date: None,
+                usage_limit: None,
+                usage: None,
+                requested: None,
+                title: None,
+            }
+            .into()
+        };
+        let invites = vec![
+            invite("https://t.me/+a"),
+            invite("https://t.me/+b"),
+            tl::enums::ExportedChatInvite::ChatInvitePublicJoinRequests,
+        ];
+
+        assert_eq!(
+            last_exported_invite(&invites).map(|invite| invite.link.as_str()),
+            Some("https://t.me/+b")
+        );
+        assert!(last_exported_invite(&invites[2..]).is_none());
+    }
+
+    #[test]
+    fn resolved_user() {
+        let users = vec![
+            tl::types::UserEmpty { id: 1 }.into(),
+            tl::types::UserEmpty { id: 2 }.into(),
+        ];
+        let peer = tl::types::PeerUser { user_id: 2 }.into();
+
+        let chat = resolved_chat(peer, users, Vec::new()).unwrap();
+        assert!(matches!(chat, Chat::User(_)));
+        assert_eq!(chat.id(), 2);
+    }
+
+    #[test]
+    fn resolved_channel() {
+        let users = vec![tl::types::UserEmpty { id: 1 }.into()];
+        let chats = vec![tl::types::ChannelForbidden {
+            broadcast: true,
+            megagroup: false,
+            id: 1,
+            access_hash: 2,
+            title: "News".into(),
+            until_date: None,
+        }
+        .into()];
+        let peer = tl::types::PeerChannel { channel_id: 1 }.into();
+
+        let chat = resolved_chat(peer, users, chats).unwrap();
+        assert!(matches!(chat, Chat::Channel(_)));
+        assert_eq!(chat.name(), "News");
+
+        let peer = tl::types::PeerChannel { channel_id: 3 }.into();
+        assert!(resolved_chat(peer, Vec::new(), Vec::new()).is_none());
+    }
+}
@@ -8,11 +8,12 @@
 use grammers_mtproto::{mtp, transport};
 use grammers_mtsender::{self as sender, retry, Sender};
 use grammers_session::{ChatHashCache, MessageBox, Session};
+use grammers_tl_types as tl;
 use sender::Enqueuer;
 use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::net::SocketAddr;
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicU32, AtomicU64};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use tokio::sync::{Mutex as AsyncMutex, RwLock as AsyncRwLock};
@@ -72,8 +73,8 @@ pub struct InitParams {
     /// seconds), but this can be disabled by passing `0` (since all flood errors would be
     /// higher and exceed the threshold).
     ///
-    /// On flood, the library will retry *once*. If the flood error occurs a second time after
-    /// sleeping, the error will be returned.
+    /// On flood, the library will sleep and retry every time the wait is within the threshold,
+    /// so a request may be retried more than once.
     pub flood_sleep_threshold: u32,
     /// How many updates may be buffered by the client at any given time.
     ///
@@ -95,6 +96,16 @@ pub struct InitParams {
     ///
     /// When the limit is `Some`, a buffer to hold that many updates will be pre-allocated.
     pub update_queue_limit: Option<usize>,
+    /// Which updates should be dropped when the `update_queue_limit` is exceeded.
+    ///
+    /// By default, the newest updates are dropped.
+    pub update_queue_overflow: UpdateQueueOverflow,
+    /// Called with the amount of updates dropped every time the `update_queue_limit` is
+    /// exceeded.
+    ///
+    /// Unlike the warning log message, this is not throttled, so it can be used to keep track of
+    /// exactly how many updates are lost. See also [`Client::dropped_update_count`].
+    pub on_updates_dropped: Option<Arc<dyn Fn(usize) + Send + Sync>>,
     /// URL of the proxy to use. Requires the `proxy` feature to be enabled.
     ///
     /// The scheme must be `socks5`. Username and password are optional.
@@ -119,12 +130,26 @@ pub struct InitParams {
     pub reconnection_policy: &'static dyn retry::RetryPolicy,
 }
 
+/// What to do with updates once the update queue is full.
+///
+/// See [`InitParams::update_queue_limit`] for more information.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UpdateQueueOverflow {
+    /// Keep the updates already queued, and drop the incoming ones.
+    #[default]
+    DropNewest,
+    /// Drop the updates which have been queued the longest to make room for the incoming ones.
+    DropOldest,
+}
+
 pub(crate) struct ClientInner {
     // Used to implement `PartialEq`.
     pub(crate) id: i64,
     pub(crate) config: Config,
     pub(crate) conn: Connection,
     pub(crate) state: RwLock<ClientState>,
+    // How many updates were dropped because the update queue was full.
+    pub(crate) dropped_updates: AtomicU64,
     // Stores per-datacenter downloader instances
     pub(crate) downloader_map: AsyncRwLock<HashMap<i32, Arc<Connection>>>,
 }
@@ -137,6 +162,8 @@ pub(crate) struct ClientState {
     // This is used to avoid spamming the log.
     pub(crate) last_update_limit_warn: Option<Instant>,
     pub(crate) updates: VecDeque<crate::types::Update>,
+    // The contacts last fetched, along with the hash identifying them.
+    pub(crate) contacts: Option<(i64, Vec<tl::enums::User>)>,
 }
 
 pub(crate) struct Connection {
@@ -188,6 +215,8 @@ impl Default for InitParams {
             server_addr: None,
             flood_sleep_threshold: 60,
             update_queue_limit: Some(100),
+            update_queue_overflow: UpdateQueueOverflow::DropNewest,
+            on_updates_dropped: None,
             #[cfg(feature = "proxy")]
             proxy_url: None,
             reconnection_policy: &grammers_mtsender::retry::NoRetry,
@@ -5,13 +5,18 @@
 // <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
-use crate::types::{ChatMap, Dialog, IterBuffer, Message};
+use crate::types::{Chat, ChatMap, Dialog, IterBuffer, Message};
 use crate::Client;
 use grammers_mtsender::InvocationError;
 use grammers_session::PackedChat;
 use grammers_tl_types as tl;
 use std::collections::HashMap;
 
+/// The folder Telegram uses for archived dialogs.
+const ARCHIVE_FOLDER_ID: i32 = 1;
+/// The folder of the main list of dialogs.
+const MAIN_FOLDER_ID: i32 = 0;
+
 const MAX_LIMIT: usize = 100;
 
 pub type DialogIter = IterBuffer<tl::functions::messages::GetDialogs, Dialog>;
@@ -257,4 +262,165 @@ impl Client {
         .await
         .map(drop)
     }
+
+    /// Moves a dialog to the archive folder.
+    ///
+    /// Chats which are not muted are moved back out of the archive when a new message arrives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.archive_dialog(&chat).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn archive_dialog<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+    ) -> Result<(), InvocationError> {
+        self.edit_peer_folders(vec![chat.into()], ARCHIVE_FOLDER_ID)
+            .await
+    }
+
+    /// Moves many dialogs to the archive folder in a single request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(a: grammers_client::types::Chat, b: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.archive_dialogs(&[&a, &b]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn archive_dialogs(&self, chats: &[&Chat]) -> Result<(), InvocationError> {
+        self.edit_peer_folders(
+            chats.iter().map(|chat| chat.pack()).collect(),
+            ARCHIVE_FOLDER_ID,
+        )
+        .await
+    }
+
+    /// Moves a dialog out of the archive folder, back to the main list of dialogs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// client.unarchive_dialog(&chat).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn unarchive_dialog<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+    ) -> Result<(), InvocationError> {
+        self.edit_peer_folders(vec![chat.into()], MAIN_FOLDER_ID)
+            .await
+    }
+
+    async fn edit_peer_folders(
+        &self,
+        chats: Vec<PackedChat>,
+        folder_id: i32,
+    ) -> Result<(), InvocationError> {
+        self.invoke(&tl::functions::folders::EditPeerFolders {
+            folder_peers: input_folder_peers(chats, folder_id),
+        })
+        .await
+        .map(drop)
+    }
+}
+
+fn input_folder_peers(chats: Vec<PackedChat>, folder_id: i32) -> Vec<tl::enums::InputFolderPeer> {
+    chats
+        .into_iter()
+        .map(|chat| {
+            tl::types::InputFolderPeer {
+                peer: chat.to_input_peer(),
+                folder_id,
+            }
+            .into()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grammers_session::PackedType;
+
+    fn folder_peer(ty: PackedType, access_hash: Option<i64>) -> tl::enums::InputFolderPeer {
+        let chat = PackedChat {
+            ty,
+            id: 1,
+            access_hash,
+        };
+        input_folder_peers(vec![chat], ARCHIVE_FOLDER_ID).remove(0)
+    }
+
+    #[test]
+    fn archive_user() {
+        assert_eq!(
+            folder_peer(PackedType::User, Some(2)),
+            tl::types::InputFolderPeer {
+                peer: tl::types::InputPeerUser {
+                    user_id: 1,
+                    access_hash: 2,
+                }
+                .into(),
+                folder_id: ARCHIVE_FOLDER_ID,
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn archive_small_group() {
+        assert_eq!(
+            folder_peer(PackedType::Chat, None),
+            tl::types::InputFolderPeer {
+                peer: tl::types::InputPeerChat { chat_id: 1 }.into(),
+                folder_id: ARCHIVE_FOLDER_ID,
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn archive_channel() {
+        assert_eq!(
+            folder_peer(PackedType::Broadcast, Some(2)),
+            tl::types::InputFolderPeer {
+                peer: tl::types::InputPeerChannel {
+                    channel_id: 1,
+                    access_hash: 2,
+                }
+                .into(),
+                folder_id: ARCHIVE_FOLDER_ID,
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn unarchive_keeps_every_chat() {
+        let chats = vec![
+            PackedChat {
+                ty: PackedType::Megagroup,
+                id: 1,
+                access_hash: Some(2),
+            },
+            PackedChat {
+                ty: PackedType::Bot,
+                id: 3,
+                access_hash: Some(4),
+            },
+        ];
+        let folder_ids = input_folder_peers(chats, MAIN_FOLDER_ID)
+            .into_iter()
+            .map(|tl::enums::InputFolderPeer::Peer(peer)| peer.folder_id)
+            .collect::<Vec<_>>();
+        assert_eq!(folder_ids, vec![MAIN_FOLDER_ID, MAIN_FOLDER_ID]);
+    }
 }
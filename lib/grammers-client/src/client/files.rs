@@ -13,11 +13,12 @@ use futures_util::stream::{FuturesUnordered, StreamExt as _};
 use grammers_mtsender::InvocationError;
 use grammers_tl_types as tl;
 use log::info;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::{io::SeekFrom, path::Path, sync::Arc};
 use tokio::sync::mpsc::unbounded_channel;
 use tokio::{
     fs,
-    io::{self, AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    io::{self, AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWrite, AsyncWriteExt},
     sync::Mutex as AsyncMutex,
 };
 
@@ -193,17 +194,66 @@ impl Client {
                 }
             }
         }
-        if downloadable.to_input_location().is_none() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "media not downloadable",
-            ));
-        }
+        check_downloadable(downloadable)?;
 
         let mut download = self.iter_download(downloadable);
         Client::load(path, &mut download).await
     }
 
+    /// Downloads a media file into the specified writer, and returns how many bytes were
+    /// written.
+    ///
+    /// The writer is not flushed nor shut down once the download completes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(downloadable: grammers_client::types::Downloadable, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut buffer = Vec::new();
+    /// let written = client.download_media_to(&downloadable, &mut buffer).await?;
+    /// assert_eq!(written as usize, buffer.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_media_to<W: AsyncWrite + Unpin>(
+        &self,
+        downloadable: &Downloadable,
+        writer: &mut W,
+    ) -> Result<u64, io::Error> {
+        self.download_media_to_with_progress(downloadable, writer, |_| {})
+            .await
+    }
+
+    /// Downloads a media file into the specified writer, reporting the progress as it goes.
+    ///
+    /// This is the same as [`Client::download_media_to`], but `on_progress` is called with the
+    /// amount of bytes written so far every time a chunk is downloaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(downloadable: grammers_client::types::Downloadable, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut file = tokio::fs::File::create("/home/username/photos/holidays.jpg").await?;
+    /// client
+    ///     .download_media_to_with_progress(&downloadable, &mut file, |written| {
+    ///         println!("Downloaded {} bytes", written);
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn download_media_to_with_progress<W: AsyncWrite + Unpin, F: Fn(u64)>(
+        &self,
+        downloadable: &Downloadable,
+        writer: &mut W,
+        on_progress: F,
+    ) -> Result<u64, io::Error> {
+        check_downloadable(downloadable)?;
+
+        let mut download = self.iter_download(downloadable);
+        Client::write_download(&mut download, writer, on_progress).await
+    }
+
     pub(crate) async fn download_media_at_location<P: AsRef<Path>>(
         &self,
         location: tl::enums::InputFileLocation,
@@ -216,15 +266,27 @@ impl Client {
 
     async fn load<P: AsRef<Path>>(path: P, download: &mut DownloadIter) -> Result<(), io::Error> {
         let mut file = fs::File::create(path).await?;
+        Client::write_download(download, &mut file, |_| {}).await?;
+        Ok(())
+    }
+
+    async fn write_download<W: AsyncWrite + Unpin, F: Fn(u64)>(
+        download: &mut DownloadIter,
+        writer: &mut W,
+        on_progress: F,
+    ) -> Result<u64, io::Error> {
+        let mut written = 0;
         while let Some(chunk) = download
             .next()
             .await
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
         {
-            file.write_all(&chunk).await?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+            on_progress(written);
         }
 
-        Ok(())
+        Ok(written)
     }
 
     /// Downloads a `Document` to specified path using multiple connections
@@ -380,6 +442,42 @@ impl Client {
         size: usize,
         name: String,
     ) -> Result<Uploaded, io::Error> {
+        self.upload_stream_with_progress(stream, size, name, |_, _| {})
+            .await
+    }
+
+    /// Uploads an async stream to Telegram servers, reporting the progress as it goes.
+    ///
+    /// This is the same as [`Client::upload_stream`], but `on_progress` is called with the
+    /// amount of bytes uploaded so far and the total `size` every time a part is uploaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client, some_vec: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    /// let size = some_vec.len();
+    /// let mut stream = std::io::Cursor::new(some_vec);
+    /// let uploaded_file = client
+    ///     .upload_stream_with_progress(&mut stream, size, "sleep.jpg".to_string(), |sent, total| {
+    ///         println!("Uploaded {} out of {} bytes", sent, total);
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_stream_with_progress<S: AsyncRead + Unpin, F: Fn(u64, u64)>(
+        &self,
+        stream: &mut S,
+        size: usize,
+        name: String,
+        on_progress: F,
+    ) -> Result<Uploaded, io::Error> {
+        let sent = AtomicU64::new(0);
+        let report = |bytes: usize| {
+            let sent = sent.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+            on_progress(sent, size as u64);
+        };
+
         let file_id = generate_random_id();
         let name = if name.is_empty() {
             "a".to_string()
@@ -397,8 +495,10 @@ impl Client {
             for _ in 0..WORKER_COUNT {
                 let handle = self.clone();
                 let parts = Arc::clone(&parts);
+                let report = &report;
                 let task = async move {
                     while let Some((part, bytes)) = parts.next_part().await? {
+                        let len = bytes.len();
                         let ok = handle
                             .invoke(&tl::functions::upload::SaveBigFilePart {
                                 file_id,
@@ -415,6 +515,7 @@ impl Client {
                                 "server failed to store uploaded data",
                             ));
                         }
+                        report(len);
                     }
                     Ok(())
                 };
@@ -437,6 +538,7 @@ impl Client {
             let mut md5 = md5::Context::new();
             while let Some((part, bytes)) = parts.next_part().await? {
                 md5.consume(&bytes);
+                let len = bytes.len();
                 let ok = self
                     .invoke(&tl::functions::upload::SaveFilePart {
                         file_id,
@@ -452,6 +554,7 @@ impl Client {
                         "server failed to store uploaded data",
                     ));
                 }
+                report(len);
             }
             Ok(Uploaded::from_raw(
                 tl::types::InputFile {
@@ -490,6 +593,35 @@ impl Client {
     ///
     /// [`InputMessage`]: crate::InputMessage
     pub async fn upload_file<P: AsRef<Path>>(&self, path: P) -> Result<Uploaded, io::Error> {
+        self.upload_file_with_progress(path, |_, _| {}).await
+    }
+
+    /// Uploads a local file to Telegram servers, reporting the progress as it goes.
+    ///
+    /// This is the same as [`Client::upload_file`], but `on_progress` is called with the amount
+    /// of bytes uploaded so far and the total size of the file every time a part is uploaded.
+    ///
+    /// Files are uploaded in parts of [`MAX_CHUNK_SIZE`] bytes. Telegram limits how many parts a
+    /// file can have, so using the largest part size is what allows uploading files of up to
+    /// 2 GB (or 4 GB for premium accounts).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let uploaded_file = client
+    ///     .upload_file_with_progress("/home/username/videos/holidays.mp4", |sent, total| {
+    ///         println!("{:.1}%", sent as f64 * 100.0 / total as f64);
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_file_with_progress<P: AsRef<Path>, F: Fn(u64, u64)>(
+        &self,
+        path: P,
+        on_progress: F,
+    ) -> Result<Uploaded, io::Error> {
         let path = path.as_ref();
 
         let mut file = fs::File::open(path).await?;
@@ -500,8 +632,19 @@ impl Client {
         // files, so it's fine to unwrap.
         let name = path.file_name().unwrap().to_string_lossy().to_string();
 
-        self.upload_stream(&mut file, size, name).await
+        self.upload_stream_with_progress(&mut file, size, name, on_progress)
+            .await
+    }
+}
+
+fn check_downloadable(downloadable: &Downloadable) -> Result<(), io::Error> {
+    if downloadable.to_input_location().is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "media not downloadable",
+        ));
     }
+    Ok(())
 }
 
 struct PartStreamInner<'a, S: AsyncRead + Unpin> {
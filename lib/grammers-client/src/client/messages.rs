@@ -7,10 +7,14 @@
 // except according to those terms.
 
 //! Methods related to sending messages.
+use crate::types::album_item::{set_spoiler, MAX_ALBUM_SIZE};
+use crate::types::message_reaction::reaction_string;
 use crate::types::{IterBuffer, Message};
-use crate::utils::{generate_random_id, generate_random_ids};
+use crate::utils::{generate_random_id, generate_random_ids, schedule_date};
 use crate::{types, ChatMap, Client};
-use chrono::{DateTime, FixedOffset};
+use chrono::{DateTime, FixedOffset, Utc};
+use futures_util::stream::{self, StreamExt as _, TryStreamExt as _};
+use grammers_mtproto::mtp::RpcError;
 pub use grammers_mtsender::{AuthorizationError, InvocationError};
 use grammers_session::PackedChat;
 use grammers_tl_types as tl;
@@ -78,6 +82,48 @@ fn map_random_ids_to_messages(
     }
 }
 
+/// Fails with the same error Telegram would return if the album is empty or too long.
+fn check_album_size(len: usize) -> Result<(), InvocationError> {
+    let name = if len == 0 {
+        "MEDIA_EMPTY"
+    } else if len > MAX_ALBUM_SIZE {
+        "MULTI_MEDIA_TOO_LONG"
+    } else {
+        return Ok(());
+    };
+    Err(InvocationError::Rpc(RpcError {
+        code: 400,
+        name: name.to_string(),
+        value: None,
+        caused_by: None,
+    }))
+}
+
+/// Find the poll contained in the message edited by the updates, if any.
+fn edited_poll(updates: tl::enums::Updates) -> Option<types::media::Poll> {
+    let updates = match updates {
+        tl::enums::Updates::Updates(updates) => updates.updates,
+        tl::enums::Updates::Combined(updates) => updates.updates,
+        tl::enums::Updates::UpdateShort(update) => vec![update.update],
+        _ => return None,
+    };
+
+    updates.into_iter().find_map(|update| {
+        let message = match update {
+            tl::enums::Update::EditMessage(u) => u.message,
+            tl::enums::Update::EditChannelMessage(u) => u.message,
+            _ => return None,
+        };
+        match message {
+            tl::enums::Message::Message(tl::types::Message {
+                media: Some(tl::enums::MessageMedia::Poll(poll)),
+                ..
+            }) => Some(types::media::Poll::from_media(poll)),
+            _ => None,
+        }
+    })
+}
+
 pub(crate) fn parse_mention_entities(
     client: &Client,
     mut entities: Vec<tl::enums::MessageEntity>,
@@ -108,7 +154,44 @@ pub(crate) fn parse_mention_entities(
     Some(entities)
 }
 
+/// The request needed to delete messages, which depends on the kind of chat they are in.
+enum DeleteMessages {
+    Channel(tl::functions::channels::DeleteMessages),
+    Chat(tl::functions::messages::DeleteMessages),
+}
+
+fn delete_messages_request(chat: PackedChat, message_ids: &[i32], revoke: bool) -> DeleteMessages {
+    if let Some(channel) = chat.try_to_input_channel() {
+        DeleteMessages::Channel(tl::functions::channels::DeleteMessages {
+            channel,
+            id: message_ids.to_vec(),
+        })
+    } else {
+        DeleteMessages::Chat(tl::functions::messages::DeleteMessages {
+            revoke,
+            id: message_ids.to_vec(),
+        })
+    }
+}
+
+/// The same request is used for all chats (there is no separate request for channels).
+fn update_pinned_request(
+    chat: PackedChat,
+    id: i32,
+    pin: bool,
+    notify: bool,
+) -> tl::functions::messages::UpdatePinnedMessage {
+    tl::functions::messages::UpdatePinnedMessage {
+        silent: !notify,
+        unpin: !pin,
+        pm_oneside: false,
+        peer: chat.to_input_peer(),
+        id,
+    }
+}
+
 const MAX_LIMIT: usize = 100;
+const ALBUM_UPLOAD_WORKERS: usize = 4;
 impl<R: tl::RemoteCall<Return = tl::enums::messages::Messages>> IterBuffer<R, Message> {
     /// Fetches the total unless cached.
     ///
@@ -222,6 +305,17 @@ impl MessageIter {
         self
     }
 
+    /// Shifts where the first page of messages starts, relative to the offset.
+    ///
+    /// A negative value can be used to start fetching messages newer than the offset. For
+    /// example, with an [`offset_id`](Self::offset_id) and an `add_offset` of `-10`, up to 10
+    /// messages newer than the offset are returned before the older ones. The shift only applies
+    /// to the first page; later pages continue from the last fetched message.
+    pub fn add_offset(mut self, add_offset: i32) -> Self {
+        self.request.add_offset = add_offset;
+        self
+    }
+
     /// Determines how many messages there are in total.
     ///
     /// This only performs a network call if `next` has not been called before.
@@ -247,6 +341,7 @@ impl MessageIter {
             let last = &self.buffer[self.buffer.len() - 1];
             self.request.offset_id = last.msg.id;
             self.request.offset_date = last.msg.date;
+            self.request.add_offset = 0;
         }
 
         Ok(self.pop_item())
@@ -310,6 +405,23 @@ impl SearchIter {
         self
     }
 
+    /// Restricts results to messages sent by the given user.
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, user: grammers_client::types::User, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut messages = client.search_messages(&chat).query("hello").sent_by(&user);
+    ///
+    /// while let Some(message) = messages.next().await? {
+    ///     println!("{} said {}", user.first_name(), message.text());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sent_by<C: Into<PackedChat>>(mut self, user: C) -> Self {
+        self.request.from_id = Some(user.into().to_input_peer());
+        self
+    }
+
     /// Returns only messages with date bigger than date_time.
     ///
     /// ```
@@ -461,6 +573,96 @@ impl GlobalSearchIter {
     }
 }
 
+const MAX_REACTION_LIMIT: usize = 100;
+
+pub type ReactionIter =
+    IterBuffer<tl::functions::messages::GetMessageReactionsList, (types::Chat, String)>;
+
+impl ReactionIter {
+    fn new(client: &Client, chat: PackedChat, message_id: i32) -> Self {
+        Self::from_request(
+            client,
+            MAX_REACTION_LIMIT,
+            tl::functions::messages::GetMessageReactionsList {
+                peer: chat.to_input_peer(),
+                id: message_id,
+                reaction: None,
+                offset: None,
+                limit: 0,
+            },
+        )
+    }
+
+    /// Only return those who reacted with the given reaction, either an emoji or the document
+    /// identifier of a custom emoji.
+    pub fn reaction(mut self, reaction: &str) -> Self {
+        self.request.reaction = Some(parse_reaction(reaction));
+        self
+    }
+
+    /// Determines how many reactions there are in total.
+    ///
+    /// This only performs a network call if `next` has not been called before.
+    pub async fn total(&mut self) -> Result<usize, InvocationError> {
+        if let Some(total) = self.total {
+            return Ok(total);
+        }
+
+        self.request.limit = 1;
+        let tl::enums::messages::MessageReactionsList::List(list) =
+            self.client.invoke(&self.request).await?;
+        let total = list.count as usize;
+        self.total = Some(total);
+        Ok(total)
+    }
+
+    /// Return the next chat that reacted along with their reaction from the internal buffer,
+    /// filling the buffer previously if it's empty.
+    ///
+    /// Custom emoji reactions are returned as the document identifier of the emoji.
+    ///
+    /// Returns `None` if the `limit` is reached or there are no reactions left.
+    pub async fn next(&mut self) -> Result<Option<(types::Chat, String)>, InvocationError> {
+        if let Some(result) = self.next_raw() {
+            return result;
+        }
+
+        self.request.limit = self.determine_limit(MAX_REACTION_LIMIT);
+        let tl::enums::messages::MessageReactionsList::List(list) =
+            self.client.invoke(&self.request).await?;
+
+        {
+            let mut state = self.client.0.state.write().unwrap();
+            // Telegram can return peers without hash (e.g. Users with 'min: true')
+            let _ = state.chat_hashes.extend(&list.users, &list.chats);
+        }
+
+        self.total = Some(list.count as usize);
+        self.last_chunk = list.next_offset.is_none() || list.reactions.is_empty();
+        self.request.offset = list.next_offset;
+
+        let chats = ChatMap::new(list.users, list.chats);
+        self.buffer.extend(peer_reactions(list.reactions, &chats));
+
+        Ok(self.pop_item())
+    }
+}
+
+/// Pair every reaction with the chat that reacted, dropping those whose chat is unknown.
+fn peer_reactions(
+    reactions: Vec<tl::enums::MessagePeerReaction>,
+    chats: &ChatMap,
+) -> Vec<(types::Chat, String)> {
+    reactions
+        .into_iter()
+        .filter_map(|reaction| {
+            let tl::enums::MessagePeerReaction::Reaction(reaction) = reaction;
+            let chat = chats.get(&reaction.peer_id)?.clone();
+            reaction_string(&reaction.reaction).map(|reaction| (chat, reaction))
+        })
+        .collect()
+}
+
 /// Method implementations related to sending, modifying or getting messages.
 impl Client {
     /// Sends a message to the desired chat.
@@ -571,6 +773,212 @@ impl Client {
         })
     }
 
+    /// Sends a poll to the chat.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use grammers_client::types::InputPoll;
+    ///
+    /// let poll = InputPoll::new("What is 2 + 2?", &["3", "4", "5"]).quiz(1, None);
+    /// client.send_poll(&chat, poll).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_poll<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        poll: types::InputPoll,
+    ) -> Result<Message, InvocationError> {
+        let mut message = types::InputMessage::text("");
+        message.media = Some(poll.into_media());
+        self.send_message(chat, message).await
+    }
+
+    /// Sends multiple uploaded files to the chat, grouped as a single album.
+    ///
+    /// Albums cannot be empty, nor have more than 10 items. The album size is checked before
+    /// anything is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use grammers_client::types::AlbumItem;
+    ///
+    /// let beach = client.upload_file("/home/username/photos/beach.jpg").await?;
+    /// let cliff = client.upload_file("/home/username/photos/cliff.jpg").await?;
+    ///
+    /// let album = vec![AlbumItem::photo(beach).caption("Holidays!"), AlbumItem::photo(cliff)];
+    /// client.send_album(&chat, album).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_album<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        items: Vec<types::AlbumItem>,
+    ) -> Result<Vec<Message>, InvocationError> {
+        check_album_size(items.len())?;
+
+        let chat = chat.into();
+        let random_ids = generate_random_ids(items.len());
+        let multi_media = stream::iter(items.into_iter().zip(random_ids.iter().copied()))
+            .map(|(item, random_id)| self.upload_album_item(chat, item, random_id))
+            .buffered(ALBUM_UPLOAD_WORKERS)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let updates = self
+            .invoke(&tl::functions::messages::SendMultiMedia {
+                silent: false,
+                background: false,
+                clear_draft: false,
+                noforwards: false,
+                update_stickersets_order: false,
+                invert_media: false,
+                peer: chat.to_input_peer(),
+                reply_to: None,
+                multi_media,
+                schedule_date: None,
+                send_as: None,
+                quick_reply_shortcut: None,
+            })
+            .await?;
+
+        Ok(map_random_ids_to_messages(self, &random_ids, updates)
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Turns the item's uploaded file into media that can be sent in an album.
+    async fn upload_album_item(
+        &self,
+        chat: PackedChat,
+        item: types::AlbumItem,
+        random_id: i64,
+    ) -> Result<tl::enums::InputSingleMedia, InvocationError> {
+        let spoiler = item.has_spoiler();
+        let (media, message) = item.into_uploaded_media();
+
+        // Albums can only contain media already stored by Telegram, not freshly-uploaded files.
+        let media = self
+            .invoke(&tl::functions::messages::UploadMedia {
+                business_connection_id: None,
+                peer: chat.to_input_peer(),
+                media,
+            })
+            .await?;
+        let id = media.constructor_id();
+        let mut media = types::Media::from_raw(media, self.clone())
+            .and_then(|media| media.to_input_media())
+            // Telegram stored the file as media which cannot be sent again.
+            .ok_or(tl::deserialize::Error::UnexpectedConstructor {
+                id,
+                expected: "MessageMedia",
+            })?;
+        set_spoiler(&mut media, spoiler);
+
+        Ok(tl::types::InputSingleMedia {
+            media,
+            random_id,
+            message,
+            entities: None,
+        }
+        .into())
+    }
+
+    /// Closes a poll sent by the logged-in user, so that no more votes can be cast.
+    ///
+    /// The closed poll is returned, and its [`results`](types::media::Poll::results) are final.
+    /// If Telegram does not send the edited message back, `None` is returned instead.
+    ///
+    /// Stopping a message that does not contain a poll, or a poll someone else sent, fails with
+    /// the error returned by Telegram.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let message_id = 123;
+    /// if let Some(poll) = client.stop_poll(&chat, message_id).await? {
+    ///     println!("{:?} people voted", poll.total_voters());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stop_poll<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        message_id: i32,
+    ) -> Result<Option<types::media::Poll>, InvocationError> {
+        let chat = chat.into();
+        // The poll can only be referenced by its identifier, so it needs to be fetched first.
+        // If there is no poll, the edit is still made so that Telegram reports the error.
+        let poll_id = match self.get_messages_by_id(chat, &[message_id]).await?.pop() {
+            Some(Some(message)) => match message.media() {
+                Some(types::Media::Poll(poll)) => poll.id(),
+                _ => 0,
+            },
+            _ => 0,
+        };
+
+        let updates = self
+            .invoke(&tl::functions::messages::EditMessage {
+                no_webpage: false,
+                invert_media: false,
+                peer: chat.to_input_peer(),
+                id: message_id,
+                message: None,
+                media: Some(types::media::Poll::closing_input_media(poll_id).into()),
+                reply_markup: None,
+                entities: None,
+                schedule_date: None,
+                quick_reply_shortcut_id: None,
+            })
+            .await?;
+
+        Ok(edited_poll(updates))
+    }
+
+    /// Schedules a message to be sent to the chat at a later date.
+    ///
+    /// This is the same as using [`InputMessage::schedule_date`] with [`Client::send_message`].
+    /// The identifier of the returned message only refers to the scheduled message, not the one
+    /// sent later.
+    ///
+    /// Bot accounts cannot schedule messages. Dates which don't fit in the timestamp Telegram
+    /// uses fail with a `SCHEDULE_DATE_INVALID` error.
+    ///
+    /// [`InputMessage::schedule_date`]: types::InputMessage::schedule_date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use chrono::{Duration, Utc};
+    ///
+    /// let send_at = Utc::now() + Duration::hours(1);
+    /// let scheduled = client.schedule_message(&chat, "Good morning!", send_at).await?;
+    /// println!("The message will be sent at {}", scheduled.send_date());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn schedule_message<C: Into<PackedChat>, M: Into<types::InputMessage>>(
+        &self,
+        chat: C,
+        message: M,
+        send_at: DateTime<Utc>,
+    ) -> Result<types::ScheduledMessage, InvocationError> {
+        let mut message = message.into();
+        message.schedule_date = Some(schedule_date(send_at)?);
+        self.send_message(chat, message)
+            .await
+            .map(types::ScheduledMessage::new)
+    }
+
     /// Edits an existing message.
     ///
     /// Similar to [`Client::send_message`], advanced formatting can be achieved with the
@@ -649,20 +1057,23 @@ impl Client {
         &self,
         chat: C,
         message_ids: &[i32],
+    ) -> Result<usize, InvocationError> {
+        self.delete_messages_with_revoke(chat.into(), message_ids, true)
+            .await
+    }
+
+    /// Like [`Client::delete_messages`], but only revoking the messages for the other end if
+    /// `revoke` is `true`. Messages in channels are always deleted for everyone.
+    pub(crate) async fn delete_messages_with_revoke(
+        &self,
+        chat: PackedChat,
+        message_ids: &[i32],
+        revoke: bool,
     ) -> Result<usize, InvocationError> {
         let tl::enums::messages::AffectedMessages::Messages(affected) =
-            if let Some(channel) = chat.into().try_to_input_channel() {
-                self.invoke(&tl::functions::channels::DeleteMessages {
-                    channel,
-                    id: message_ids.to_vec(),
-                })
-                .await
-            } else {
-                self.invoke(&tl::functions::messages::DeleteMessages {
-                    revoke: true,
-                    id: message_ids.to_vec(),
-                })
-                .await
+            match delete_messages_request(chat, message_ids, revoke) {
+                DeleteMessages::Channel(request) => self.invoke(&request).await,
+                DeleteMessages::Chat(request) => self.invoke(&request).await,
             }?;
 
         Ok(affected.pts_count as usize)
@@ -697,20 +1108,34 @@ impl Client {
         destination: C,
         message_ids: &[i32],
         source: S,
+    ) -> Result<Vec<Option<Message>>, InvocationError> {
+        self.forward_messages_with(destination.into(), message_ids, source.into(), false, None)
+            .await
+    }
+
+    /// Like [`Client::forward_messages`], but with the option to forward the messages silently
+    /// (without notifying), or to schedule them to be sent at the given date.
+    pub(crate) async fn forward_messages_with(
+        &self,
+        destination: PackedChat,
+        message_ids: &[i32],
+        source: PackedChat,
+        silent: bool,
+        schedule_date: Option<i32>,
     ) -> Result<Vec<Option<Message>>, InvocationError> {
         // TODO let user customize more options
         let request = tl::functions::messages::ForwardMessages {
-            silent: false,
+            silent,
             background: false,
             with_my_score: false,
             drop_author: false,
             drop_media_captions: false,
-            from_peer: source.into().to_input_peer(),
+            from_peer: source.to_input_peer(),
             id: message_ids.to_vec(),
             random_id: generate_random_ids(message_ids.len()),
-            to_peer: destination.into().to_input_peer(),
+            to_peer: destination.to_input_peer(),
             top_msg_id: None,
-            schedule_date: None,
+            schedule_date,
             send_as: None,
             noforwards: false,
             quick_reply_shortcut: None,
@@ -918,6 +1343,46 @@ impl Client {
         Ok(message_ids.iter().map(|id| map.remove(id)).collect())
     }
 
+    /// Get all the messages scheduled to be sent to the chat by the logged-in user.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// for scheduled in client.get_scheduled_messages(&chat).await? {
+    ///     println!("{:?} will be sent at {}", scheduled.message().text(), scheduled.send_date());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_scheduled_messages<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+    ) -> Result<Vec<types::ScheduledMessage>, InvocationError> {
+        let result = self
+            .invoke(&tl::functions::messages::GetScheduledHistory {
+                peer: chat.into().to_input_peer(),
+                hash: 0,
+            })
+            .await?;
+
+        let (messages, users, chats) = match result {
+            tl::enums::messages::Messages::Messages(m) => (m.messages, m.users, m.chats),
+            tl::enums::messages::Messages::Slice(m) => (m.messages, m.users, m.chats),
+            tl::enums::messages::Messages::ChannelMessages(m) => (m.messages, m.users, m.chats),
+            tl::enums::messages::Messages::NotModified(_) => {
+                panic!("API returned Messages::NotModified even though hash = 0")
+            }
+        };
+
+        let chats = ChatMap::new(users, chats);
+        Ok(messages
+            .into_iter()
+            .flat_map(|m| Message::new(self, m, &chats))
+            .map(types::ScheduledMessage::new)
+            .collect())
+    }
+
     /// Get the latest pin from a chat.
     ///
     /// # Examples
@@ -964,14 +1429,16 @@ impl Client {
             .find(|m| m.chat().pack() == chat))
     }
 
-    /// Pin a message in the chat. This will not notify any users.
+    /// Pin a message in the chat.
+    ///
+    /// If `notify` is `true`, the members of the chat will be notified about the pin.
     ///
     /// # Examples
     ///
     /// ```
     /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
     /// let message_id = 123;
-    /// client.pin_message(&chat, message_id).await?;
+    /// client.pin_message(&chat, message_id, false).await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -980,8 +1447,10 @@ impl Client {
         &self,
         chat: C,
         message_id: i32,
+        notify: bool,
     ) -> Result<(), InvocationError> {
-        self.update_pinned(chat.into(), message_id, true).await
+        self.update_pinned(chat.into(), message_id, true, notify)
+            .await
     }
 
     /// Unpin a message from the chat.
@@ -1000,7 +1469,8 @@ impl Client {
         chat: C,
         message_id: i32,
     ) -> Result<(), InvocationError> {
-        self.update_pinned(chat.into(), message_id, false).await
+        self.update_pinned(chat.into(), message_id, false, false)
+            .await
     }
 
     async fn update_pinned(
@@ -1008,16 +1478,11 @@ impl Client {
         chat: PackedChat,
         id: i32,
         pin: bool,
+        notify: bool,
     ) -> Result<(), InvocationError> {
-        self.invoke(&tl::functions::messages::UpdatePinnedMessage {
-            silent: true,
-            unpin: !pin,
-            pm_oneside: false,
-            peer: chat.to_input_peer(),
-            id,
-        })
-        .await
-        .map(drop)
+        self.invoke(&update_pinned_request(chat, id, pin, notify))
+            .await
+            .map(drop)
     }
 
     /// Unpin all currently-pinned messages from the chat.
@@ -1041,4 +1506,310 @@ impl Client {
         .await?;
         Ok(())
     }
+
+    /// Reacts to a message, replacing the previous reaction of the logged-in user, if any.
+    ///
+    /// The reaction can be either an emoji, or the document identifier of a custom emoji.
+    ///
+    /// See also: [`Client::remove_reaction`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let message_id = 123;
+    /// client.react_to_message(&chat, message_id, "👍").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn react_to_message<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        message_id: i32,
+        reaction: &str,
+    ) -> Result<(), InvocationError> {
+        self.send_reaction(chat.into(), message_id, Some(parse_reaction(reaction)))
+            .await
+    }
+
+    /// Removes the reaction of the logged-in user from a message.
+    ///
+    /// See also: [`Client::react_to_message`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let message_id = 123;
+    /// client.remove_reaction(&chat, message_id).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn remove_reaction<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        message_id: i32,
+    ) -> Result<(), InvocationError> {
+        self.send_reaction(chat.into(), message_id, None).await
+    }
+
+    /// Iterate over the users and chats who reacted to a message, along with their reaction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(chat: grammers_client::types::Chat, client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let message_id = 123;
+    /// let mut reactions = client.iter_reactions(&chat, message_id).reaction("👍");
+    ///
+    /// while let Some((chat, reaction)) = reactions.next().await? {
+    ///     println!("{} reacted with {}", chat.name(), reaction);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_reactions<C: Into<PackedChat>>(&self, chat: C, message_id: i32) -> ReactionIter {
+        ReactionIter::new(self, chat.into(), message_id)
+    }
+
+    async fn send_reaction(
+        &self,
+        chat: PackedChat,
+        message_id: i32,
+        reaction: Option<tl::enums::Reaction>,
+    ) -> Result<(), InvocationError> {
+        self.invoke(&tl::functions::messages::SendReaction {
+            big: false,
+            add_to_recent: true,
+            peer: chat.to_input_peer(),
+            msg_id: message_id,
+            reaction: reaction.map(|reaction| vec![reaction]),
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+/// Custom emoji are referred to by their document identifier, which no regular emoji can be.
+fn parse_reaction(reaction: &str) -> tl::enums::Reaction {
+    match reaction.parse() {
+        Ok(document_id) => tl::types::ReactionCustomEmoji { document_id }.into(),
+        Err(_) => tl::types::ReactionEmoji {
+            emoticon: reaction.to_string(),
+        }
+        .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grammers_session::PackedType;
+
+    fn packed_chat(ty: PackedType) -> PackedChat {
+        PackedChat {
+            ty,
+            id: 123,
+            access_hash: Some(456),
+        }
+    }
+
+    #[test]
+    fn pin_forwards_notify_flag() {
+        for ty in [PackedType::Megagroup, PackedType::Chat, PackedType::User] {
+            let request = update_pinned_request(packed_chat(ty), 7, true, true);
+            assert_eq!(request.id, 7);
+            assert!(!request.silent);
+            assert!(!request.unpin);
+
+            let request = update_pinned_request(packed_chat(ty), 7, true, false);
+            assert!(request.silent);
+        }
+    }
+
+    #[test]
+    fn unpin_sets_unpin_flag() {
+        let request = update_pinned_request(packed_chat(PackedType::Broadcast), 7, false, false);
+        assert!(request.unpin);
+        assert!(matches!(request.peer, tl::enums::InputPeer::Channel(_)));
+    }
+
+    #[test]
+    fn edited_poll_is_found() {
+        let mut media = types::media::Poll::closing_input_media(42);
+        let tl::enums::Poll::Poll(poll) = &mut media.poll;
+        poll.question = tl::types::TextWithEntities {
+            text: "Done?".to_string(),
+            entities: Vec::new(),
+        }
+        .into();
+
+        let message = tl::types::Message {
+            out: true,
+            mentioned: false,
+            media_unread: false,
+            silent: false,
+            post: false,
+            from_scheduled: false,
+            legacy: false,
+            edit_hide: false,
+            pinned: false,
+            noforwards: false,
+            invert_media: false,
+            id: 7,
+            from_id: None,
+            from_boosts_applied: None,
+            peer_id: tl::types::PeerUser { user_id: 123 }.into(),
+            saved_peer_id: None,
+            fwd_from: None,
+            via_bot_id: None,
+            reply_to: None,
+            date: 0,
+            message: String::new(),
+            media: Some(
+                tl::types::MessageMediaPoll {
+                    poll: media.poll,
+                    results: tl::types::PollResults {
+                        min: false,
+                        results: None,
+                        total_voters: Some(3),
+                        recent_voters: None,
+                        solution: None,
+                        solution_entities: None,
+                    }
+                    .into(),
+                }
+                .into(),
+            ),
+            reply_markup: None,
+            entities: None,
+            views: None,
+            forwards: None,
+            replies: None,
+            edit_date: None,
+            post_author: None,
+            grouped_id: None,
+            restriction_reason: None,
+            ttl_period: None,
+            reactions: None,
+            quick_reply_shortcut_id: None,
+            via_business_bot_id: None,
+            offline: false,
+        };
+        let updates = tl::types::UpdateShort {
+            update: tl::types::UpdateEditMessage {
+                message: message.into(),
+                pts: 1,
+                pts_count: 1,
+            }
+            .into(),
+            date: 0,
+        }
+        .into();
+
+        let poll = edited_poll(updates).unwrap();
+        assert!(poll.closed());
+        assert_eq!(poll.id(), 42);
+        assert_eq!(poll.total_voters(), Some(3));
+    }
+
+    #[test]
+    fn reactions_are_parsed() {
+        assert_eq!(
+            parse_reaction("👍"),
+            tl::types::ReactionEmoji {
+                emoticon: "👍".to_string()
+            }
+            .into()
+        );
+        assert_eq!(
+            parse_reaction("5368324170671202286"),
+            tl::types::ReactionCustomEmoji {
+                document_id: 5368324170671202286
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn album_size_is_checked() {
+        assert!(check_album_size(1).is_ok());
+        assert!(check_album_size(10).is_ok());
+        assert!(check_album_size(0).unwrap_err().is("MEDIA_EMPTY"));
+        assert!(check_album_size(11).unwrap_err().is("MULTI_MEDIA_TOO_LONG"));
+    }
+
+    #[test]
+    fn schedule_date_must_fit() {
+        let last = i64::from(i32::MAX);
+        let date = |timestamp| DateTime::from_timestamp(timestamp, 0).unwrap();
+        assert_eq!(schedule_date(date(last)).unwrap(), i32::MAX);
+        assert!(schedule_date(date(last + 1))
+            .unwrap_err()
+            .is("SCHEDULE_DATE_INVALID"));
+    }
+
+    #[test]
+    fn no_peer_reactions() {
+        assert!(peer_reactions(Vec::new(), &ChatMap::empty()).is_empty());
+    }
+
+    #[test]
+    fn peer_reactions_need_known_chats() {
+        let reaction = |user_id, reaction| {
+            tl::types::MessagePeerReaction {
+                big: false,
+                unread: false,
+                my: false,
+                peer_id: tl::types::PeerUser { user_id }.into(),
+                date: 0,
+                reaction,
+            }
+            .into()
+        };
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 1 }.into()], Vec::new());
+
+        let reactions = peer_reactions(
+            vec![
+                reaction(1, parse_reaction("👍")),
+                reaction(1, parse_reaction("123")),
+                reaction(2, parse_reaction("👍")),
+            ],
+            &chats,
+        );
+        let reactions = reactions
+            .iter()
+            .map(|(chat, reaction)| (chat.id(), reaction.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(reactions, [(1, "👍"), (1, "123")]);
+    }
+
+    #[test]
+    fn delete_in_supergroup_uses_channel_request() {
+        match delete_messages_request(packed_chat(PackedType::Megagroup), &[1, 2], true) {
+            DeleteMessages::Channel(request) => {
+                assert_eq!(request.id, [1, 2]);
+                assert!(matches!(
+                    request.channel,
+                    tl::enums::InputChannel::Channel(_)
+                ));
+            }
+            DeleteMessages::Chat(_) => panic!("supergroups should use channels.deleteMessages"),
+        }
+    }
+
+    #[test]
+    fn delete_in_private_chat_uses_messages_request() {
+        for revoke in [true, false] {
+            match delete_messages_request(packed_chat(PackedType::User), &[1], revoke) {
+                DeleteMessages::Chat(request) => {
+                    assert_eq!(request.id, [1]);
+                    assert_eq!(request.revoke, revoke);
+                }
+                DeleteMessages::Channel(_) => {
+                    panic!("private chats should use messages.deleteMessages")
+                }
+            }
+        }
+    }
 }
@@ -18,4 +18,4 @@ pub mod updates;
 
 pub use auth::SignInError;
 pub(crate) use client::ClientInner;
-pub use client::{Client, Config, InitParams};
+pub use client::{Client, Config, InitParams, UpdateQueueOverflow};
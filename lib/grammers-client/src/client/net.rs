@@ -17,7 +17,7 @@ use log::{debug, info};
 use sender::Enqueuer;
 use std::collections::{HashMap, VecDeque};
 use std::net::{Ipv4Addr, SocketAddr};
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::sync::oneshot::error::TryRecvError;
@@ -197,7 +197,9 @@ impl Client {
                 chat_hashes: ChatHashCache::new(self_user.map(|u| (u.id, u.bot))),
                 last_update_limit_warn: None,
                 updates,
+                contacts: None,
             }),
+            dropped_updates: AtomicU64::new(0),
             downloader_map: AsyncRwLock::new(HashMap::new()),
         }));
 
@@ -256,6 +258,37 @@ impl Client {
             .await
     }
 
+    /// Invoke a raw API call, giving up if the response does not arrive within `timeout`.
+    ///
+    /// Behaves like [`Client::invoke`], but returns [`InvocationError::Timeout`] once the timeout
+    /// expires. The time spent sleeping on flood waits counts towards the timeout.
+    ///
+    /// Giving up only means the response is no longer awaited. The request may have already been
+    /// sent, in which case Telegram will still process it, and its response will be discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use grammers_tl_types as tl;
+    /// use std::time::Duration;
+    ///
+    /// let pong = client
+    ///     .invoke_with_timeout(&tl::functions::Ping { ping_id: 0 }, Duration::from_secs(10))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn invoke_with_timeout<R: tl::RemoteCall>(
+        &self,
+        request: &R,
+        timeout: Duration,
+    ) -> Result<R::Return, InvocationError> {
+        tokio::time::timeout(timeout, self.invoke(request))
+            .await
+            .unwrap_or(Err(InvocationError::Timeout))
+    }
+
     async fn export_authorization(
         &self,
         target_dc_id: i32,
@@ -375,43 +408,31 @@ impl Connection {
     pub(crate) async fn invoke<R: tl::RemoteCall, F: Fn(Vec<tl::enums::Updates>) -> ()>(
         &self,
         request: &R,
-        _flood_sleep_threshold: u32,
+        flood_sleep_threshold: u32,
         on_updates: F,
     ) -> Result<R::Return, InvocationError> {
-        const GENERIC_ERROR_TIMEOUT: u64 = 5;
-
-        let mut exp_backoff = 0;
+        let mut retry = RetryState::default();
 
         let mut rx = { self.request_tx.read().unwrap().enqueue(request) };
         loop {
             match rx.try_recv() {
                 Ok(response) => match response {
                     Ok(body) => break R::Return::from_bytes(&body).map_err(|e| e.into()),
-                    // we automagically retry on:
-                    //   500  - internal server error on tg side
-                    //   -503 - timedout
-                    //   420  - FLOOD_WAIT
-                    Err(InvocationError::Rpc(RpcError {
-                        name, code, value, ..
-                    })) if code == 500 || code == -503 || code == 420 => {
-                        let delay = if code == 420 {
-                            value.map(|v| v as u64).unwrap_or(GENERIC_ERROR_TIMEOUT)
-                        } else {
-                            GENERIC_ERROR_TIMEOUT
-                        } * (1 << exp_backoff);
-                        info!(
-                            "sleeping on {} for {:?} before retrying {}",
-                            name,
-                            delay,
-                            std::any::type_name::<R>()
-                        );
-                        tokio::time::sleep(Duration::from_secs(delay)).await;
-                        rx = self.request_tx.read().unwrap().enqueue(request);
-                        exp_backoff += 1;
-
-                        continue;
-                    }
-                    Err(e) => break Err(e),
+                    Err(e) => match retry.delay(&e, flood_sleep_threshold) {
+                        Some(delay) => {
+                            info!(
+                                "sleeping on {} for {:?} before retrying {}",
+                                e,
+                                delay,
+                                std::any::type_name::<R>()
+                            );
+                            tokio::time::sleep(delay).await;
+                            rx = self.request_tx.read().unwrap().enqueue(request);
+
+                            continue;
+                        }
+                        None => break Err(e),
+                    },
                 },
                 Err(TryRecvError::Empty) => {
                     on_updates(self.step().await?);
@@ -438,3 +459,103 @@ impl Connection {
         }
     }
 }
+
+/// Decides whether a request that failed should be sent again, and how long to sleep before.
+#[derive(Default)]
+struct RetryState {
+    exp_backoff: u32,
+}
+
+impl RetryState {
+    fn delay(&mut self, error: &InvocationError, flood_sleep_threshold: u32) -> Option<Duration> {
+        const GENERIC_ERROR_TIMEOUT: u64 = 5;
+
+        match error {
+            // we automagically retry on:
+            //   500  - internal server error on tg side
+            //   -503 - timedout
+            InvocationError::Rpc(RpcError { code, .. }) if *code == 500 || *code == -503 => {
+                let delay = GENERIC_ERROR_TIMEOUT * (1 << self.exp_backoff);
+                self.exp_backoff += 1;
+                Some(Duration::from_secs(delay))
+            }
+            // and on every flood wait short enough to sleep on.
+            _ => flood_sleep_delay(error, flood_sleep_threshold),
+        }
+    }
+}
+
+/// How long to sleep before retrying after `error`, if it is a flood wait within the threshold
+/// (in seconds).
+fn flood_sleep_delay(error: &InvocationError, flood_sleep_threshold: u32) -> Option<Duration> {
+    error
+        .flood_wait()
+        .filter(|delay| delay.as_secs() <= flood_sleep_threshold as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_error(message: &str) -> InvocationError {
+        InvocationError::Rpc(
+            tl::types::RpcError {
+                error_code: 420,
+                error_message: message.to_string(),
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn flood_sleep_within_threshold() {
+        assert_eq!(
+            flood_sleep_delay(&rpc_error("FLOOD_WAIT_17"), 20),
+            Some(Duration::from_secs(17))
+        );
+        assert_eq!(
+            flood_sleep_delay(&rpc_error("SLOWMODE_WAIT_20"), 20),
+            Some(Duration::from_secs(20))
+        );
+    }
+
+    #[test]
+    fn flood_sleep_above_threshold() {
+        assert_eq!(flood_sleep_delay(&rpc_error("FLOOD_WAIT_21"), 20), None);
+        assert_eq!(flood_sleep_delay(&rpc_error("FLOOD_WAIT_1"), 0), None);
+    }
+
+    #[test]
+    fn flood_sleep_consecutive_waits() {
+        let mut retry = RetryState::default();
+        assert_eq!(
+            retry.delay(&rpc_error("FLOOD_WAIT_3"), 20),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            retry.delay(&rpc_error("FLOOD_WAIT_5"), 20),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(retry.delay(&rpc_error("FLOOD_WAIT_21"), 20), None);
+    }
+
+    #[test]
+    fn server_errors_back_off() {
+        let mut retry = RetryState::default();
+        let error = InvocationError::Rpc(
+            tl::types::RpcError {
+                error_code: 500,
+                error_message: "INTERNAL".to_string(),
+            }
+            .into(),
+        );
+        assert_eq!(retry.delay(&error, 0), Some(Duration::from_secs(5)));
+        assert_eq!(retry.delay(&error, 0), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn flood_sleep_other_errors() {
+        assert_eq!(flood_sleep_delay(&rpc_error("PEER_FLOOD"), 60), None);
+        assert_eq!(flood_sleep_delay(&InvocationError::Dropped, 60), None);
+    }
+}
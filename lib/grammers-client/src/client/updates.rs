@@ -8,15 +8,18 @@
 
 //! Methods to deal with and offer access to updates.
 
-use super::Client;
-use crate::types::{ChatMap, Update};
+use super::{Client, UpdateQueueOverflow};
+use crate::types::{ChatMap, Message, Update};
 use futures_util::future::{select, Either};
+use futures_util::stream::{self, Stream};
 pub use grammers_mtsender::{AuthorizationError, InvocationError};
 use grammers_session::channel_id;
 pub use grammers_session::{PrematureEndReason, UpdateState};
 use grammers_tl_types as tl;
 use log::warn;
+use std::collections::VecDeque;
 use std::pin::pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::sleep_until;
@@ -49,6 +52,41 @@ impl Client {
     /// # }
     /// ```
     pub async fn next_update(&self) -> Result<Option<Update>, InvocationError> {
+        self.next_update_until(None).await
+    }
+
+    /// Returns the next update from the buffer where they are queued until used, waiting at most
+    /// for the given `timeout` for one to arrive.
+    ///
+    /// This behaves the same as [`Client::next_update`], except that `None` is also returned
+    /// when the timeout expires before any update is available. This is useful to periodically
+    /// perform some other work even if no updates arrive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    ///
+    /// loop {
+    ///     match client.next_update_with_timeout(Duration::from_secs(5)).await? {
+    ///         Some(update) => println!("{:?}", update),
+    ///         None => println!("no updates in the last 5 seconds"),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn next_update_with_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<Update>, InvocationError> {
+        self.next_update_until(Some(Instant::now() + timeout)).await
+    }
+
+    async fn next_update_until(
+        &self,
+        timeout: Option<Instant>,
+    ) -> Result<Option<Update>, InvocationError> {
         loop {
             let (deadline, get_diff, get_channel_diff) = {
                 let state = &mut *self.0.state.write().unwrap();
@@ -153,6 +191,12 @@ impl Client {
                 continue;
             }
 
+            let deadline = match timeout {
+                Some(timeout) if timeout <= Instant::now() => return Ok(None),
+                Some(timeout) => deadline.min(timeout),
+                None => deadline,
+            };
+
             let step = {
                 let sleep = pin!(async { sleep_until(deadline.into()).await });
                 let step = pin!(async { self.step().await });
@@ -169,6 +213,132 @@ impl Client {
         }
     }
 
+    /// Returns the next update from the buffer where they are queued until used, if any.
+    ///
+    /// Unlike [`Client::next_update`], this method does not wait for updates to arrive, nor does
+    /// it drive the connection to fetch them. It is up to the caller to ensure the connection is
+    /// being driven elsewhere (for example, with [`Client::step`]), or no updates will be queued.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn f(client: grammers_client::Client) {
+    /// while let Some(update) = client.try_next_update() {
+    ///     println!("{:?}", update);
+    /// }
+    /// # }
+    /// ```
+    pub fn try_next_update(&self) -> Option<Update> {
+        self.0.state.write().unwrap().updates.pop_front()
+    }
+
+    /// Returns how many updates are queued in the buffer, waiting to be returned by
+    /// [`Client::next_update`].
+    pub fn update_queue_len(&self) -> usize {
+        self.0.state.read().unwrap().updates.len()
+    }
+
+    /// Discards all the updates queued in the buffer.
+    ///
+    /// The discarded updates are lost and will not be returned by [`Client::next_update`].
+    /// Telegram still considers them delivered, so they won't be fetched again either. However,
+    /// the update state itself is kept, so updates received afterwards are not affected.
+    pub fn clear_update_queue(&self) {
+        self.0.state.write().unwrap().updates.clear();
+    }
+
+    /// Returns a stream over the updates, which can be used wherever a [`Stream`] is expected.
+    ///
+    /// The stream polls [`Client::next_update`] until no more updates are available. Errors are
+    /// yielded as-is and do not end the stream, so it is up to the caller to decide whether they
+    /// should stop polling for updates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures_util::StreamExt as _;
+    /// use std::pin::pin;
+    ///
+    /// let mut updates = pin!(client.stream_updates());
+    /// while let Some(update) = updates.next().await {
+    ///     println!("{:?}", update?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_updates(&self) -> impl Stream<Item = Result<Update, InvocationError>> + Send {
+        stream::unfold(self.clone(), |client| async move {
+            match client.next_update().await {
+                Ok(Some(update)) => Some((Ok(update), client)),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), client)),
+            }
+        })
+    }
+
+    /// Collects the rest of the album the given message belongs to.
+    ///
+    /// Telegram sends each message in an album (several photos or videos sent together) as a
+    /// separate update, all of them sharing the same [`Message::grouped_id`]. This method waits
+    /// up to `timeout` for the remaining messages of the album to arrive, and returns them all,
+    /// starting with `first`.
+    ///
+    /// If `first` is not part of an album, it is returned on its own without waiting. Any other
+    /// update received in the meantime is put back into the queue, in the same order, so that it
+    /// is returned by the next call to [`Client::next_update`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// use grammers_client::Update;
+    /// use std::time::Duration;
+    ///
+    /// while let Some(update) = client.next_update().await? {
+    ///     if let Update::NewMessage(message) = update {
+    ///         let album = client.collect_album(message, Duration::from_secs(1)).await?;
+    ///         println!("received {} message(s) at once", album.len());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collect_album(
+        &self,
+        first: Message,
+        timeout: Duration,
+    ) -> Result<Vec<Message>, InvocationError> {
+        let grouped_id = match first.grouped_id() {
+            Some(id) => id,
+            None => return Ok(vec![first]),
+        };
+
+        let deadline = Instant::now() + timeout;
+        let mut album = vec![first];
+        let mut deferred = Vec::new();
+        let result = loop {
+            match self.next_update_until(Some(deadline)).await {
+                Ok(Some(Update::NewMessage(message)))
+                    if message.grouped_id() == Some(grouped_id)
+                        && message.msg.peer_id == album[0].msg.peer_id =>
+                {
+                    album.push(message);
+                }
+                Ok(Some(update)) => deferred.push(update),
+                Ok(None) => break Ok(album),
+                Err(e) => break Err(e),
+            }
+        };
+
+        let state = &mut *self.0.state.write().unwrap();
+        for update in deferred.into_iter().rev() {
+            state.updates.push_front(update);
+        }
+
+        result
+    }
+
     pub(crate) fn process_socket_updates(&self, all_updates: Vec<tl::enums::Updates>) {
         if all_updates.is_empty() {
             return;
@@ -211,21 +381,26 @@ impl Client {
 
     fn extend_update_queue(&self, mut updates: Vec<tl::enums::Update>, chat_map: Arc<ChatMap>) {
         let mut state = self.0.state.write().unwrap();
+        let mut dropped = 0;
 
         if let Some(limit) = self.0.config.params.update_queue_limit {
-            if let Some(exceeds) = (state.updates.len() + updates.len()).checked_sub(limit + 1) {
-                let exceeds = exceeds + 1;
+            let overflow = self.0.config.params.update_queue_overflow;
+            dropped = drop_overflowing(&mut state.updates, &mut updates, limit, overflow);
+            if dropped > 0 {
+                self.0
+                    .dropped_updates
+                    .fetch_add(dropped as u64, Ordering::Relaxed);
+
                 let now = Instant::now();
                 let notify = match state.last_update_limit_warn {
                     None => true,
                     Some(instant) => now - instant > UPDATE_LIMIT_EXCEEDED_LOG_COOLDOWN,
                 };
 
-                updates.truncate(updates.len() - exceeds);
                 if notify {
                     warn!(
                         "{} updates were dropped because the update_queue_limit was exceeded",
-                        exceeds
+                        dropped
                     );
                 }
 
@@ -238,6 +413,20 @@ impl Client {
                 .into_iter()
                 .flat_map(|u| Update::new(self, u, &chat_map)),
         );
+        drop(state);
+
+        // Called without holding the lock so that the callback may use the client.
+        if dropped > 0 {
+            if let Some(on_updates_dropped) = &self.0.config.params.on_updates_dropped {
+                on_updates_dropped(dropped);
+            }
+        }
+    }
+
+    /// Returns how many updates have been dropped so far because the
+    /// [`InitParams::update_queue_limit`](crate::InitParams::update_queue_limit) was exceeded.
+    pub fn dropped_update_count(&self) -> u64 {
+        self.0.dropped_updates.load(Ordering::Relaxed)
     }
 
     /// Synchronize the updates state to the session.
@@ -250,6 +439,31 @@ impl Client {
     }
 }
 
+/// Drops updates according to the overflow policy so that at most `limit` remain once the
+/// incoming updates are queued, and returns how many were dropped.
+fn drop_overflowing<T, U>(
+    queued: &mut VecDeque<T>,
+    incoming: &mut Vec<U>,
+    limit: usize,
+    overflow: UpdateQueueOverflow,
+) -> usize {
+    let exceeds = (queued.len() + incoming.len()).saturating_sub(limit);
+    match overflow {
+        UpdateQueueOverflow::DropNewest => {
+            let dropped = exceeds.min(incoming.len());
+            incoming.truncate(incoming.len() - dropped);
+            dropped
+        }
+        UpdateQueueOverflow::DropOldest => {
+            let from_queue = exceeds.min(queued.len());
+            queued.drain(..from_queue);
+            let from_incoming = exceeds - from_queue;
+            incoming.drain(..from_incoming);
+            exceeds
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,12 +473,83 @@ mod tests {
         panic!()
     }
 
+    fn get_message() -> Message {
+        panic!()
+    }
+
     #[test]
     fn ensure_next_update_future_impls_send() {
         if false {
             // We just want it to type-check, not actually run.
             fn typeck(_: impl Future + Send) {}
             typeck(get_client().next_update());
+            typeck(get_client().next_update_with_timeout(Duration::ZERO));
+            typeck(get_client().collect_album(get_message(), Duration::ZERO));
+        }
+    }
+
+    #[test]
+    fn ensure_stream_updates_impls_send() {
+        if false {
+            // We just want it to type-check, not actually run.
+            fn typeck(_: impl Stream + Send) {}
+            typeck(get_client().stream_updates());
+        }
+    }
+
+    #[test]
+    fn overflow_drops_newest_incoming_updates() {
+        let mut queued = VecDeque::from([1, 2, 3]);
+        let mut incoming = vec![4, 5, 6];
+        let dropped = drop_overflowing(
+            &mut queued,
+            &mut incoming,
+            4,
+            UpdateQueueOverflow::DropNewest,
+        );
+        assert_eq!(dropped, 2);
+        assert_eq!(queued, [1, 2, 3]);
+        assert_eq!(incoming, [4]);
+    }
+
+    #[test]
+    fn overflow_drops_oldest_queued_updates_first() {
+        let mut queued = VecDeque::from([1, 2, 3]);
+        let mut incoming = vec![4, 5, 6];
+        let dropped = drop_overflowing(
+            &mut queued,
+            &mut incoming,
+            4,
+            UpdateQueueOverflow::DropOldest,
+        );
+        assert_eq!(dropped, 2);
+        assert_eq!(queued, [3]);
+        assert_eq!(incoming, [4, 5, 6]);
+
+        let mut queued = VecDeque::from([1]);
+        let mut incoming = vec![2, 3, 4];
+        let dropped = drop_overflowing(
+            &mut queued,
+            &mut incoming,
+            2,
+            UpdateQueueOverflow::DropOldest,
+        );
+        assert_eq!(dropped, 2);
+        assert!(queued.is_empty());
+        assert_eq!(incoming, [3, 4]);
+    }
+
+    #[test]
+    fn no_overflow_drops_nothing() {
+        let mut queued = VecDeque::from([1]);
+        let mut incoming = vec![2];
+        for overflow in [
+            UpdateQueueOverflow::DropNewest,
+            UpdateQueueOverflow::DropOldest,
+        ] {
+            assert_eq!(drop_overflowing(&mut queued, &mut incoming, 2, overflow), 0);
         }
+        assert_eq!(queued, [1]);
+        assert_eq!(incoming, [2]);
     }
 }
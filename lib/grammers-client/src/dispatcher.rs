@@ -0,0 +1,251 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Routing of updates to handlers.
+//!
+//! See [`Dispatcher`] for more information.
+
+use crate::types::{CallbackQuery, InlineQuery, Message};
+use crate::{Client, Update};
+use futures_util::future::BoxFuture;
+use grammers_mtsender::InvocationError;
+use log::warn;
+use std::fmt;
+use std::future::Future;
+
+type HandlerResult = Result<(), InvocationError>;
+type Filter = Box<dyn Fn(&Update) -> bool + Send + Sync>;
+type HandlerFn = Box<dyn Fn(Client, Update) -> BoxFuture<'static, HandlerResult> + Send + Sync>;
+
+struct Handler {
+    filter: Filter,
+    handler: HandlerFn,
+}
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Handler(usize),
+    Fallback,
+    Drop,
+}
+
+/// Routes every update to the first handler registered for it.
+///
+/// Handlers are tried in the order they were registered, and only the first one whose filter
+/// accepts the update is called. Updates no handler accepts are passed to the fallback handler,
+/// if any, or dropped otherwise.
+///
+/// Errors returned by handlers are logged, and do not stop the dispatcher.
+///
+/// # Examples
+///
+/// ```
+/// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use grammers_client::{Dispatcher, Update};
+///
+/// Dispatcher::new()
+///     .on(
+///         |update| matches!(update, Update::NewMessage(m) if m.text() == "/start"),
+///         |_client, update| async move {
+///             if let Update::NewMessage(message) = update {
+///                 message.reply("Hello!").await?;
+///             }
+///             Ok(())
+///         },
+///     )
+///     .on_new_message(|_client, message| async move {
+///         println!("Got a message: {}", message.text());
+///         Ok(())
+///     })
+///     .fallback(|_client, update| async move {
+///         println!("Unhandled update: {:?}", update);
+///         Ok(())
+///     })
+///     .run(&client)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Dispatcher {
+    handlers: Vec<Handler>,
+    fallback: Option<HandlerFn>,
+}
+
+fn boxed<H, F>(handler: H) -> HandlerFn
+where
+    H: Fn(Client, Update) -> F + Send + Sync + 'static,
+    F: Future<Output = HandlerResult> + Send + 'static,
+{
+    Box::new(move |client, update| Box::pin(handler(client, update)))
+}
+
+impl Dispatcher {
+    /// Creates a new dispatcher without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for the updates accepted by the given filter.
+    pub fn on<P, H, F>(mut self, filter: P, handler: H) -> Self
+    where
+        P: Fn(&Update) -> bool + Send + Sync + 'static,
+        H: Fn(Client, Update) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        self.handlers.push(Handler {
+            filter: Box::new(filter),
+            handler: boxed(handler),
+        });
+        self
+    }
+
+    /// Registers a handler for [`Update::NewMessage`].
+    pub fn on_new_message<H, F>(mut self, handler: H) -> Self
+    where
+        H: Fn(Client, Message) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        self.handlers.push(Handler {
+            filter: Box::new(|update| matches!(update, Update::NewMessage(_))),
+            handler: Box::new(move |client, update| match update {
+                Update::NewMessage(message) => Box::pin(handler(client, message)),
+                _ => skip(),
+            }),
+        });
+        self
+    }
+
+    /// Registers a handler for [`Update::CallbackQuery`].
+    pub fn on_callback_query<H, F>(mut self, handler: H) -> Self
+    where
+        H: Fn(Client, CallbackQuery) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        self.handlers.push(Handler {
+            filter: Box::new(|update| matches!(update, Update::CallbackQuery(_))),
+            handler: Box::new(move |client, update| match update {
+                Update::CallbackQuery(query) => Box::pin(handler(client, query)),
+                _ => skip(),
+            }),
+        });
+        self
+    }
+
+    /// Registers a handler for [`Update::InlineQuery`].
+    pub fn on_inline_query<H, F>(mut self, handler: H) -> Self
+    where
+        H: Fn(Client, InlineQuery) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        self.handlers.push(Handler {
+            filter: Box::new(|update| matches!(update, Update::InlineQuery(_))),
+            handler: Box::new(move |client, update| match update {
+                Update::InlineQuery(query) => Box::pin(handler(client, query)),
+                _ => skip(),
+            }),
+        });
+        self
+    }
+
+    /// Sets the handler for the updates no other handler accepts.
+    pub fn fallback<H, F>(mut self, handler: H) -> Self
+    where
+        H: Fn(Client, Update) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        self.fallback = Some(boxed(handler));
+        self
+    }
+
+    /// The handler the update should be routed to.
+    fn route(&self, update: &Update) -> Route {
+        match self.handlers.iter().position(|h| (h.filter)(update)) {
+            Some(index) => Route::Handler(index),
+            None if self.fallback.is_some() => Route::Fallback,
+            None => Route::Drop,
+        }
+    }
+
+    /// Routes a single update to its handler, and returns the result of the handler.
+    ///
+    /// Nothing is done if no handler accepts the update.
+    pub async fn dispatch(&self, client: &Client, update: Update) -> HandlerResult {
+        let handler = match self.route(&update) {
+            Route::Handler(index) => &self.handlers[index].handler,
+            Route::Fallback => self.fallback.as_ref().unwrap(),
+            Route::Drop => return Ok(()),
+        };
+        handler(client.clone(), update).await
+    }
+
+    /// Routes every update returned by [`Client::next_update`] to its handler, until there are
+    /// no more updates or fetching them fails.
+    pub async fn run(&self, client: &Client) -> HandlerResult {
+        while let Some(update) = client.next_update().await? {
+            if let Err(e) = self.dispatch(client, update).await {
+                warn!("update handler failed: {}", e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Typed handlers are only called with updates their filter accepted, so this is never used.
+fn skip<'a>() -> BoxFuture<'a, HandlerResult> {
+    Box::pin(async { Ok(()) })
+}
+
+impl fmt::Debug for Dispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dispatcher")
+            .field("handlers", &self.handlers.len())
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grammers_tl_types as tl;
+
+    fn status_update(user_id: i64) -> Update {
+        Update::UserStatusUpdate {
+            user_id,
+            user: None,
+            status: tl::enums::UserStatus::Empty,
+        }
+    }
+
+    #[test]
+    fn first_matching_handler_is_chosen() {
+        let dispatcher = Dispatcher::new()
+            .on_new_message(|_, _| async { Ok(()) })
+            .on(
+                |u| matches!(u, Update::UserStatusUpdate { user_id: 1, .. }),
+                |_, _| async { Ok(()) },
+            )
+            .on(
+                |u| matches!(u, Update::UserStatusUpdate { .. }),
+                |_, _| async { Ok(()) },
+            );
+
+        assert_eq!(dispatcher.route(&status_update(1)), Route::Handler(1));
+        assert_eq!(dispatcher.route(&status_update(2)), Route::Handler(2));
+    }
+
+    #[test]
+    fn unmatched_updates_use_fallback() {
+        let dispatcher = Dispatcher::new().on_new_message(|_, _| async { Ok(()) });
+        assert_eq!(dispatcher.route(&status_update(1)), Route::Drop);
+
+        let dispatcher = dispatcher.fallback(|_, _| async { Ok(()) });
+        assert_eq!(dispatcher.route(&status_update(1)), Route::Fallback);
+    }
+}
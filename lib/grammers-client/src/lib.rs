@@ -37,6 +37,7 @@
 //! [Telegram Bot API]: https://core.telegram.org/bots/api
 //! [obtain a developer API ID]: https://my.telegram.org/auth
 pub mod client;
+pub mod dispatcher;
 #[cfg(not(feature = "unstable_raw"))]
 mod parsers;
 #[cfg(feature = "unstable_raw")]
@@ -44,5 +45,6 @@ pub mod parsers;
 pub mod types;
 pub(crate) mod utils;
 
-pub use client::{Client, Config, InitParams, SignInError};
+pub use client::{Client, Config, InitParams, SignInError, UpdateQueueOverflow};
+pub use dispatcher::Dispatcher;
 pub use types::{button, reply_markup, ChatMap, InputMessage, Update};
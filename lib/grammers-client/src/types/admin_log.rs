@@ -0,0 +1,451 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{ChatMap, Message, Role, User};
+use crate::utils;
+use crate::Client;
+use chrono::{DateTime, Utc};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// An entry in the log of administrative actions taken in a group or channel.
+#[derive(Clone)]
+pub struct AdminLogEvent {
+    raw: tl::types::ChannelAdminLogEvent,
+    action: AdminLogAction,
+    chats: Arc<ChatMap>,
+}
+
+/// What an administrator, or sometimes a participant, did in a group or channel.
+///
+/// Actions without a variant of their own are kept as they were sent by Telegram in
+/// [`AdminLogAction::Other`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum AdminLogAction {
+    /// The title of the chat was changed.
+    ChangeTitle { prev: String, new: String },
+    /// The description of the chat was changed.
+    ChangeAbout { prev: String, new: String },
+    /// The public username of the chat was changed. An empty username means there was none.
+    ChangeUsername { prev: String, new: String },
+    /// A message was pinned, or unpinned.
+    UpdatePinned(Box<Message>),
+    /// A message was edited.
+    EditMessage {
+        prev: Box<Message>,
+        new: Box<Message>,
+    },
+    /// A message was deleted.
+    DeleteMessage(Box<Message>),
+    /// The actor joined the chat.
+    ParticipantJoin,
+    /// The actor left the chat.
+    ParticipantLeave,
+    /// The participant with the given identifier was invited by the actor.
+    ParticipantInvite { participant_id: i64, role: Role },
+    /// The participant with the given identifier was banned, unbanned, or had their
+    /// restrictions changed.
+    ParticipantToggleBan {
+        participant_id: i64,
+        prev: Role,
+        new: Role,
+    },
+    /// The participant with the given identifier was promoted, demoted, or had their
+    /// administrator rights changed.
+    ParticipantToggleAdmin {
+        participant_id: i64,
+        prev: Role,
+        new: Role,
+    },
+    /// Any other action, as sent by Telegram.
+    Other(Box<tl::enums::ChannelAdminLogEventAction>),
+}
+
+impl AdminLogEvent {
+    pub(crate) fn new(
+        client: &Client,
+        event: tl::enums::ChannelAdminLogEvent,
+        chats: &Arc<ChatMap>,
+    ) -> Self {
+        let tl::enums::ChannelAdminLogEvent::Event(raw) = event;
+        Self {
+            action: AdminLogAction::from_raw(client, raw.action.clone(), chats),
+            raw,
+            chats: chats.clone(),
+        }
+    }
+
+    /// The identifier of this event, unique within the chat.
+    pub fn id(&self) -> i64 {
+        self.raw.id
+    }
+
+    /// When the action took place.
+    pub fn date(&self) -> DateTime<Utc> {
+        utils::date(self.raw.date)
+    }
+
+    /// The identifier of the user who performed the action.
+    pub fn actor_id(&self) -> i64 {
+        self.raw.user_id
+    }
+
+    /// The user who performed the action, if it's known.
+    pub fn actor(&self) -> Option<&User> {
+        self.chats.get_user(self.raw.user_id)
+    }
+
+    /// The action that was performed.
+    pub fn action(&self) -> &AdminLogAction {
+        &self.action
+    }
+
+    /// The raw event as sent by Telegram.
+    pub fn raw(&self) -> &tl::types::ChannelAdminLogEvent {
+        &self.raw
+    }
+}
+
+impl fmt::Debug for AdminLogEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminLogEvent")
+            .field("id", &self.id())
+            .field("date", &self.date())
+            .field("actor_id", &self.actor_id())
+            .field("action", &self.action())
+            .finish()
+    }
+}
+
+impl AdminLogAction {
+    fn from_raw(
+        client: &Client,
+        action: tl::enums::ChannelAdminLogEventAction,
+        chats: &Arc<ChatMap>,
+    ) -> Self {
+        use tl::enums::ChannelAdminLogEventAction as A;
+
+        let message = |message: &tl::enums::Message| {
+            Message::new(client, message.clone(), chats).map(Box::new)
+        };
+
+        match &action {
+            A::ChangeTitle(a) => Self::ChangeTitle {
+                prev: a.prev_value.clone(),
+                new: a.new_value.clone(),
+            },
+            A::ChangeAbout(a) => Self::ChangeAbout {
+                prev: a.prev_value.clone(),
+                new: a.new_value.clone(),
+            },
+            A::ChangeUsername(a) => Self::ChangeUsername {
+                prev: a.prev_value.clone(),
+                new: a.new_value.clone(),
+            },
+            A::UpdatePinned(a) => match message(&a.message) {
+                Some(message) => Self::UpdatePinned(message),
+                None => Self::Other(Box::new(action)),
+            },
+            A::EditMessage(a) => match (message(&a.prev_message), message(&a.new_message)) {
+                (Some(prev), Some(new)) => Self::EditMessage { prev, new },
+                _ => Self::Other(Box::new(action)),
+            },
+            A::DeleteMessage(a) => match message(&a.message) {
+                Some(message) => Self::DeleteMessage(message),
+                None => Self::Other(Box::new(action)),
+            },
+            A::ParticipantJoin => Self::ParticipantJoin,
+            A::ParticipantLeave => Self::ParticipantLeave,
+            A::ParticipantInvite(a) => Self::ParticipantInvite {
+                participant_id: participant_id(&a.participant),
+                role: Role::from_raw_channel(a.participant.clone()),
+            },
+            A::ParticipantToggleBan(a) => Self::ParticipantToggleBan {
+                participant_id: participant_id(&a.new_participant),
+                prev: Role::from_raw_channel(a.prev_participant.clone()),
+                new: Role::from_raw_channel(a.new_participant.clone()),
+            },
+            A::ParticipantToggleAdmin(a) => Self::ParticipantToggleAdmin {
+                participant_id: participant_id(&a.new_participant),
+                prev: Role::from_raw_channel(a.prev_participant.clone()),
+                new: Role::from_raw_channel(a.new_participant.clone()),
+            },
+            _ => Self::Other(Box::new(action)),
+        }
+    }
+}
+
+/// The identifier of the user, or of the chat for banned chats, a participant refers to.
+fn participant_id(participant: &tl::enums::ChannelParticipant) -> i64 {
+    use tl::enums::ChannelParticipant as P;
+
+    let peer = match participant {
+        P::Participant(p) => return p.user_id,
+        P::ParticipantSelf(p) => return p.user_id,
+        P::Creator(p) => return p.user_id,
+        P::Admin(p) => return p.user_id,
+        P::Banned(p) => &p.peer,
+        P::Left(p) => &p.peer,
+    };
+    match peer {
+        tl::enums::Peer::User(p) => p.user_id,
+        tl::enums::Peer::Chat(p) => p.chat_id,
+        tl::enums::Peer::Channel(p) => p.channel_id,
+    }
+}
+
+/// Which kinds of events should be returned by [`AdminLogIter`](crate::client::chats::AdminLogIter).
+///
+/// If no kind is enabled, which is the default, events of every kind are returned.
+///
+/// # Examples
+///
+/// ```
+/// use grammers_client::types::AdminLogFilter;
+///
+/// // Only bans, and messages which were edited or deleted.
+/// let filter = AdminLogFilter::new().ban(true).edit(true).delete(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AdminLogFilter {
+    raw: tl::types::ChannelAdminLogEventsFilter,
+}
+
+impl Default for AdminLogFilter {
+    fn default() -> Self {
+        Self {
+            raw: tl::types::ChannelAdminLogEventsFilter {
+                join: false,
+                leave: false,
+                invite: false,
+                ban: false,
+                unban: false,
+                kick: false,
+                unkick: false,
+                promote: false,
+                demote: false,
+                info: false,
+                settings: false,
+                pinned: false,
+                edit: false,
+                delete: false,
+                group_call: false,
+                invites: false,
+                send: false,
+                forums: false,
+            },
+        }
+    }
+}
+
+impl AdminLogFilter {
+    /// Creates a filter which lets events of every kind through.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include users joining the chat.
+    pub fn join(mut self, val: bool) -> Self {
+        self.raw.join = val;
+        self
+    }
+
+    /// Include users leaving the chat.
+    pub fn leave(mut self, val: bool) -> Self {
+        self.raw.leave = val;
+        self
+    }
+
+    /// Include users being invited to the chat.
+    pub fn invite(mut self, val: bool) -> Self {
+        self.raw.invite = val;
+        self
+    }
+
+    /// Include users being banned or restricted.
+    pub fn ban(mut self, val: bool) -> Self {
+        self.raw.ban = val;
+        self
+    }
+
+    /// Include users being unbanned or having their restrictions lifted.
+    pub fn unban(mut self, val: bool) -> Self {
+        self.raw.unban = val;
+        self
+    }
+
+    /// Include users being kicked.
+    pub fn kick(mut self, val: bool) -> Self {
+        self.raw.kick = val;
+        self
+    }
+
+    /// Include users being unkicked.
+    pub fn unkick(mut self, val: bool) -> Self {
+        self.raw.unkick = val;
+        self
+    }
+
+    /// Include users being promoted to administrators.
+    pub fn promote(mut self, val: bool) -> Self {
+        self.raw.promote = val;
+        self
+    }
+
+    /// Include administrators being demoted.
+    pub fn demote(mut self, val: bool) -> Self {
+        self.raw.demote = val;
+        self
+    }
+
+    /// Include changes to the chat's information, such as its title, photo or description.
+    pub fn info(mut self, val: bool) -> Self {
+        self.raw.info = val;
+        self
+    }
+
+    /// Include changes to the chat's settings.
+    pub fn settings(mut self, val: bool) -> Self {
+        self.raw.settings = val;
+        self
+    }
+
+    /// Include messages being pinned.
+    pub fn pinned(mut self, val: bool) -> Self {
+        self.raw.pinned = val;
+        self
+    }
+
+    /// Include messages being edited.
+    pub fn edit(mut self, val: bool) -> Self {
+        self.raw.edit = val;
+        self
+    }
+
+    /// Include messages being deleted.
+    pub fn delete(mut self, val: bool) -> Self {
+        self.raw.delete = val;
+        self
+    }
+
+    /// Include group calls being started, ended or changed.
+    pub fn group_call(mut self, val: bool) -> Self {
+        self.raw.group_call = val;
+        self
+    }
+
+    /// Include invite links being created, edited or revoked.
+    pub fn invites(mut self, val: bool) -> Self {
+        self.raw.invites = val;
+        self
+    }
+
+    /// Include messages being sent.
+    pub fn send(mut self, val: bool) -> Self {
+        self.raw.send = val;
+        self
+    }
+
+    /// Include forum topics being created, edited or deleted.
+    pub fn forums(mut self, val: bool) -> Self {
+        self.raw.forums = val;
+        self
+    }
+
+    /// The filter to send to Telegram, or `None` if events of every kind should be returned.
+    pub(crate) fn into_raw(self) -> Option<tl::enums::ChannelAdminLogEventsFilter> {
+        let r = &self.raw;
+        let any = r.join
+            || r.leave
+            || r.invite
+            || r.ban
+            || r.unban
+            || r.kick
+            || r.unkick
+            || r.promote
+            || r.demote
+            || r.info
+            || r.settings
+            || r.pinned
+            || r.edit
+            || r.delete
+            || r.group_call
+            || r.invites
+            || r.send
+            || r.forums;
+        if any {
+            Some(self.raw.into())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_is_not_sent() {
+        assert!(AdminLogFilter::new().into_raw().is_none());
+    }
+
+    #[test]
+    fn filter_sets_fields() {
+        let tl::enums::ChannelAdminLogEventsFilter::Filter(raw) = AdminLogFilter::new()
+            .ban(true)
+            .delete(true)
+            .into_raw()
+            .unwrap();
+        assert!(raw.ban);
+        assert!(raw.delete);
+        assert!(!raw.unban);
+        assert!(!raw.join);
+    }
+
+    #[test]
+    fn participant_id_of_banned_peer() {
+        let banned = tl::types::ChannelParticipantBanned {
+            left: false,
+            peer: tl::types::PeerChannel { channel_id: 7 }.into(),
+            kicked_by: 1,
+            date: 0,
+            banned_rights: tl::types::ChatBannedRights {
+                view_messages: true,
+                send_messages: false,
+                send_media: false,
+                send_stickers: false,
+                send_gifs: false,
+                send_games: false,
+                send_inline: false,
+                embed_links: false,
+                send_polls: false,
+                change_info: false,
+                invite_users: false,
+                pin_messages: false,
+                manage_topics: false,
+                send_photos: false,
+                send_videos: false,
+                send_roundvideos: false,
+                send_audios: false,
+                send_voices: false,
+                send_docs: false,
+                send_plain: false,
+                until_date: 0,
+            }
+            .into(),
+        };
+        assert_eq!(participant_id(&banned.into()), 7);
+
+        let left = tl::types::ChannelParticipantLeft {
+            peer: tl::types::PeerUser { user_id: 3 }.into(),
+        };
+        assert_eq!(participant_id(&left.into()), 3);
+    }
+}
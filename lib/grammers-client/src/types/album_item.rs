@@ -0,0 +1,152 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{InputMessage, Uploaded};
+use grammers_tl_types as tl;
+
+/// Telegram does not allow more than this many items in a single album.
+pub(crate) const MAX_ALBUM_SIZE: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Photo,
+    Document,
+}
+
+/// An uploaded file to be sent as part of an album with [`Client::send_album`].
+///
+/// Albums can contain either photos and videos, or documents of the same type, but not both.
+///
+/// [`Client::send_album`]: crate::Client::send_album
+///
+/// # Examples
+///
+/// ```
+/// # async fn f(client: grammers_client::Client) -> Result<(), Box<dyn std::error::Error>> {
+/// use grammers_client::types::AlbumItem;
+///
+/// let beach = client.upload_file("/home/username/photos/beach.jpg").await?;
+/// let cliff = client.upload_file("/home/username/photos/cliff.jpg").await?;
+///
+/// let album = vec![
+///     AlbumItem::photo(beach).caption("The beach"),
+///     AlbumItem::photo(cliff).spoiler(true),
+/// ];
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AlbumItem {
+    file: Uploaded,
+    kind: Kind,
+    caption: String,
+    spoiler: bool,
+}
+
+impl AlbumItem {
+    fn new(file: Uploaded, kind: Kind) -> Self {
+        Self {
+            file,
+            kind,
+            caption: String::new(),
+            spoiler: false,
+        }
+    }
+
+    /// The uploaded file will be sent as a photo, which the Telegram server will compress.
+    pub fn photo(file: Uploaded) -> Self {
+        Self::new(file, Kind::Photo)
+    }
+
+    /// The uploaded file will be sent as a document, such as a video or an uncompressed photo.
+    pub fn document(file: Uploaded) -> Self {
+        Self::new(file, Kind::Document)
+    }
+
+    /// The caption shown under this item.
+    pub fn caption(mut self, caption: &str) -> Self {
+        self.caption = caption.to_string();
+        self
+    }
+
+    /// Whether the item should be hidden behind a spoiler animation.
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.spoiler = spoiler;
+        self
+    }
+
+    pub(crate) fn has_spoiler(&self) -> bool {
+        self.spoiler
+    }
+
+    /// The media to upload with `messages.uploadMedia`, along with the caption of the item.
+    pub(crate) fn into_uploaded_media(self) -> (tl::enums::InputMedia, String) {
+        let message = InputMessage::text(self.caption);
+        let message = match self.kind {
+            Kind::Photo => message.photo(self.file),
+            Kind::Document => message.document(self.file),
+        };
+        (message.media.unwrap(), message.text)
+    }
+}
+
+/// Sets the spoiler flag of media which was already uploaded.
+pub(crate) fn set_spoiler(media: &mut tl::enums::InputMedia, spoiler: bool) {
+    match media {
+        tl::enums::InputMedia::Photo(photo) => photo.spoiler = spoiler,
+        tl::enums::InputMedia::Document(document) => document.spoiler = spoiler,
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uploaded(name: &str) -> Uploaded {
+        Uploaded::from_raw(
+            tl::types::InputFile {
+                id: 1,
+                parts: 1,
+                name: name.to_string(),
+                md5_checksum: String::new(),
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn photo_item_becomes_uploaded_photo() {
+        let item = AlbumItem::photo(uploaded("beach.jpg")).caption("The beach");
+
+        let (media, caption) = item.into_uploaded_media();
+        assert!(matches!(media, tl::enums::InputMedia::UploadedPhoto(_)));
+        assert_eq!(caption, "The beach");
+    }
+
+    #[test]
+    fn document_item_becomes_uploaded_document() {
+        let (media, caption) = AlbumItem::document(uploaded("notes.txt")).into_uploaded_media();
+        assert!(matches!(media, tl::enums::InputMedia::UploadedDocument(_)));
+        assert_eq!(caption, "");
+    }
+
+    #[test]
+    fn spoiler_is_set_on_sent_media() {
+        let mut media = tl::types::InputMediaPhoto {
+            spoiler: false,
+            id: tl::enums::InputPhoto::Empty,
+            ttl_seconds: None,
+        }
+        .into();
+        set_spoiler(&mut media, true);
+        assert!(matches!(
+            media,
+            tl::enums::InputMedia::Photo(tl::types::InputMediaPhoto { spoiler: true, .. })
+        ));
+    }
+}
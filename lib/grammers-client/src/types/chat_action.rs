@@ -0,0 +1,116 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{Chat, ChatMap};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// Occurs whenever someone is performing an action in a chat, such as typing or uploading a
+/// photo.
+#[derive(Clone)]
+pub struct ChatAction {
+    chat: tl::enums::Peer,
+    sender: tl::enums::Peer,
+    action: tl::enums::SendMessageAction,
+    chats: Arc<ChatMap>,
+}
+
+impl ChatAction {
+    /// Creates a new `ChatAction` if the update is about someone performing an action.
+    pub(crate) fn new(update: tl::enums::Update, chats: &Arc<ChatMap>) -> Option<Self> {
+        use tl::enums::Update as U;
+
+        let (chat, sender, action) = match update {
+            U::UserTyping(u) => {
+                let peer: tl::enums::Peer = tl::types::PeerUser { user_id: u.user_id }.into();
+                (peer, peer, u.action)
+            }
+            U::ChatUserTyping(u) => (
+                tl::types::PeerChat { chat_id: u.chat_id }.into(),
+                u.from_id,
+                u.action,
+            ),
+            U::ChannelUserTyping(u) => (
+                tl::types::PeerChannel {
+                    channel_id: u.channel_id,
+                }
+                .into(),
+                u.from_id,
+                u.action,
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            chat,
+            sender,
+            action,
+            chats: chats.clone(),
+        })
+    }
+
+    /// The chat where the action is being performed, if it's known.
+    ///
+    /// For private conversations, this is the same as the [`ChatAction::sender`].
+    pub fn chat(&self) -> Option<&Chat> {
+        self.chats.get(&self.chat)
+    }
+
+    /// The user or channel performing the action, if it's known.
+    pub fn sender(&self) -> Option<&Chat> {
+        self.chats.get(&self.sender)
+    }
+
+    /// The action being performed.
+    pub fn action(&self) -> &tl::enums::SendMessageAction {
+        &self.action
+    }
+}
+
+impl fmt::Debug for ChatAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatAction")
+            .field("chat", &self.chat())
+            .field("sender", &self.sender())
+            .field("action", &self.action)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_typing_becomes_chat_action() {
+        let update = tl::types::UpdateUserTyping {
+            user_id: 1,
+            action: tl::enums::SendMessageAction::SendMessageTypingAction,
+        };
+
+        let action = ChatAction::new(update.into(), &ChatMap::empty()).unwrap();
+        assert_eq!(
+            action.action(),
+            &tl::enums::SendMessageAction::SendMessageTypingAction
+        );
+    }
+
+    #[test]
+    fn unknown_channel_is_none() {
+        let update = tl::types::UpdateChannelUserTyping {
+            channel_id: 1,
+            top_msg_id: None,
+            from_id: tl::types::PeerUser { user_id: 2 }.into(),
+            action: tl::enums::SendMessageAction::SendMessageCancelAction,
+        };
+
+        let action = ChatAction::new(update.into(), &ChatMap::empty()).unwrap();
+        assert!(action.chat().is_none());
+        assert!(action.sender().is_none());
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{Chat, ChatMap, User};
+use crate::utils;
+use chrono::{DateTime, Utc};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// Occurs whenever a user asks to join a group or channel where new members need to be approved.
+///
+/// This update only occurs for bots which are administrators in the chat and can invite users.
+#[derive(Clone)]
+pub struct ChatJoinRequest {
+    raw: tl::types::UpdateBotChatInviteRequester,
+    chats: Arc<ChatMap>,
+}
+
+impl ChatJoinRequest {
+    pub(crate) fn new(raw: tl::types::UpdateBotChatInviteRequester, chats: &Arc<ChatMap>) -> Self {
+        Self {
+            raw,
+            chats: chats.clone(),
+        }
+    }
+
+    /// The group or channel the user wants to join, if it's known.
+    pub fn chat(&self) -> Option<&Chat> {
+        self.chats.get(&self.raw.peer)
+    }
+
+    /// The identifier of the user who wants to join.
+    pub fn user_id(&self) -> i64 {
+        self.raw.user_id
+    }
+
+    /// The user who wants to join, if it's known.
+    pub fn user(&self) -> Option<&User> {
+        self.chats.get_user(self.raw.user_id)
+    }
+
+    /// When the user asked to join.
+    pub fn date(&self) -> DateTime<Utc> {
+        utils::date(self.raw.date)
+    }
+
+    /// The bio of the user who wants to join.
+    pub fn about(&self) -> &str {
+        &self.raw.about
+    }
+
+    /// The invite link the user used to ask to join, if they used one instead of asking from
+    /// the public chat.
+    pub fn invite_link(&self) -> Option<&str> {
+        match &self.raw.invite {
+            tl::enums::ExportedChatInvite::ChatInviteExported(invite) => Some(&invite.link),
+            tl::enums::ExportedChatInvite::ChatInvitePublicJoinRequests => None,
+        }
+    }
+
+    /// The raw update as sent by Telegram.
+    pub fn raw(&self) -> &tl::types::UpdateBotChatInviteRequester {
+        &self.raw
+    }
+}
+
+impl fmt::Debug for ChatJoinRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatJoinRequest")
+            .field("chat", &self.chat())
+            .field("user_id", &self.user_id())
+            .field("date", &self.date())
+            .field("about", &self.about())
+            .field("invite_link", &self.invite_link())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(invite: tl::enums::ExportedChatInvite) -> ChatJoinRequest {
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 2 }.into()], Vec::new());
+        let update = tl::types::UpdateBotChatInviteRequester {
+            peer: tl::types::PeerChannel { channel_id: 1 }.into(),
+            date: 3,
+            user_id: 2,
+            about: "Hi!".to_string(),
+            invite,
+            qts: 4,
+        };
+        ChatJoinRequest::new(update, &chats)
+    }
+
+    #[test]
+    fn join_request_through_invite_link() {
+        let request = request(
+            tl::types::ChatInviteExported {
+                revoked: false,
+                permanent: false,
+                request_needed: true,
+                link: "https://t.me/+abc".to_string(),
+                admin_id: 5,
+                date: 0,
+                start_date: None,
+                expire_date: None,
+                usage_limit: None,
+                usage: None,
+                requested: None,
+                title: None,
+            }
+            .into(),
+        );
+
+        assert!(request.chat().is_none());
+        assert_eq!(request.user().map(|u| u.id()), Some(2));
+        assert_eq!(request.date().timestamp(), 3);
+        assert_eq!(request.about(), "Hi!");
+        assert_eq!(request.invite_link(), Some("https://t.me/+abc"));
+    }
+
+    #[test]
+    fn join_request_from_public_chat() {
+        let request = request(tl::enums::ExportedChatInvite::ChatInvitePublicJoinRequests);
+        assert_eq!(request.invite_link(), None);
+    }
+}
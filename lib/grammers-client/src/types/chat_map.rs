@@ -70,6 +70,14 @@ impl ChatMap {
         self.map.get(&peer.into())
     }
 
+    /// Retrieve the full `User` object given its identifier.
+    pub(crate) fn get_user(&self, user_id: i64) -> Option<&User> {
+        match self.map.get(&Peer::User(user_id)) {
+            Some(Chat::User(user)) => Some(user),
+            _ => None,
+        }
+    }
+
     /// Take the full `Chat` object given its `Peer` and remove it from the map.
     pub fn remove(&mut self, peer: &tl::enums::Peer) -> Option<Chat> {
         self.map.remove(&peer.into())
@@ -84,3 +92,15 @@ impl ChatMap {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn users_are_found_by_id() {
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 1 }.into()], Vec::new());
+        assert_eq!(chats.get_user(1).map(|user| user.id()), Some(1));
+        assert!(chats.get_user(2).is_none());
+    }
+}
@@ -0,0 +1,217 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::participant::{Left, Normal};
+use super::{Chat, ChatMap, Role, User};
+use crate::utils;
+use chrono::{DateTime, Utc};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// Occurs whenever a participant joins, leaves, or has their role changed in a group or channel.
+///
+/// Depending on what Telegram sends, the previous or new role of the participant may be unknown.
+#[derive(Clone)]
+pub struct ChatMemberUpdate {
+    chat: tl::enums::Peer,
+    user_id: i64,
+    actor_id: Option<i64>,
+    date: Option<i32>,
+    old_participant: Option<Role>,
+    new_participant: Option<Role>,
+    chats: Arc<ChatMap>,
+}
+
+impl ChatMemberUpdate {
+    /// Creates a new `ChatMemberUpdate` if the update is about a chat member changing.
+    pub(crate) fn new(update: tl::enums::Update, chats: &Arc<ChatMap>) -> Option<Self> {
+        use tl::enums::Update as U;
+
+        let (chat, user_id, actor_id, date, old_participant, new_participant) = match update {
+            U::ChatParticipantAdd(u) => (
+                tl::types::PeerChat { chat_id: u.chat_id }.into(),
+                u.user_id,
+                Some(u.inviter_id),
+                Some(u.date),
+                None,
+                Some(Role::User(Normal {
+                    date: u.date,
+                    inviter_id: Some(u.inviter_id),
+                })),
+            ),
+            U::ChatParticipantDelete(u) => (
+                tl::types::PeerChat { chat_id: u.chat_id }.into(),
+                u.user_id,
+                None,
+                None,
+                None,
+                Some(Role::Left(Left {})),
+            ),
+            U::ChatParticipant(u) => (
+                tl::types::PeerChat { chat_id: u.chat_id }.into(),
+                u.user_id,
+                Some(u.actor_id),
+                Some(u.date),
+                u.prev_participant.map(Role::from_raw_chat),
+                u.new_participant.map(Role::from_raw_chat),
+            ),
+            U::ChannelParticipant(u) => (
+                tl::types::PeerChannel {
+                    channel_id: u.channel_id,
+                }
+                .into(),
+                u.user_id,
+                Some(u.actor_id),
+                Some(u.date),
+                u.prev_participant.map(Role::from_raw_channel),
+                u.new_participant.map(Role::from_raw_channel),
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            chat,
+            user_id,
+            actor_id,
+            date,
+            old_participant,
+            new_participant,
+            chats: chats.clone(),
+        })
+    }
+
+    /// The group or channel where the participant changed, if it's known.
+    pub fn chat(&self) -> Option<&Chat> {
+        self.chats.get(&self.chat)
+    }
+
+    /// The identifier of the user whose participation changed.
+    pub fn user_id(&self) -> i64 {
+        self.user_id
+    }
+
+    /// The user whose participation changed, if it's known.
+    pub fn user(&self) -> Option<&User> {
+        self.chats.get_user(self.user_id)
+    }
+
+    /// The identifier of the user who caused the change, if it's known.
+    ///
+    /// This is the same as [`ChatMemberUpdate::user_id`] if the participant joined or left on
+    /// their own.
+    pub fn actor_id(&self) -> Option<i64> {
+        self.actor_id
+    }
+
+    /// The user who caused the change, if it's known.
+    pub fn actor(&self) -> Option<&User> {
+        self.actor_id.and_then(|id| self.chats.get_user(id))
+    }
+
+    /// The role the participant had before the change, if it's known.
+    pub fn old_participant(&self) -> Option<&Role> {
+        self.old_participant.as_ref()
+    }
+
+    /// The role the participant has after the change, if it's known.
+    ///
+    /// A participant leaving or being removed from a small group has the [`Role::Left`] role.
+    pub fn new_participant(&self) -> Option<&Role> {
+        self.new_participant.as_ref()
+    }
+
+    /// When the change occurred, if it's known.
+    pub fn date(&self) -> Option<DateTime<Utc>> {
+        self.date.map(utils::date)
+    }
+}
+
+impl fmt::Debug for ChatMemberUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatMemberUpdate")
+            .field("chat", &self.chat())
+            .field("user_id", &self.user_id)
+            .field("actor_id", &self.actor_id)
+            .field("old_participant", &self.old_participant)
+            .field("new_participant", &self.new_participant)
+            .field("date", &self.date())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_participant_becomes_chat_member_update() {
+        let update = tl::types::UpdateChatParticipant {
+            chat_id: 1,
+            date: 2,
+            actor_id: 3,
+            user_id: 4,
+            prev_participant: None,
+            new_participant: Some(
+                tl::types::ChatParticipantAdmin {
+                    user_id: 4,
+                    inviter_id: 3,
+                    date: 2,
+                }
+                .into(),
+            ),
+            invite: None,
+            qts: 5,
+        };
+
+        let member = ChatMemberUpdate::new(update.into(), &ChatMap::empty()).unwrap();
+        assert_eq!(member.user_id(), 4);
+        assert_eq!(member.actor_id(), Some(3));
+        assert!(member.actor().is_none());
+        assert!(member.chat().is_none());
+        assert!(member.old_participant().is_none());
+        assert!(matches!(member.new_participant(), Some(Role::Admin(_))));
+        assert_eq!(member.date().map(|d| d.timestamp()), Some(2));
+    }
+
+    #[test]
+    fn channel_participant_becomes_chat_member_update() {
+        let update = tl::types::UpdateChannelParticipant {
+            via_chatlist: false,
+            channel_id: 1,
+            date: 2,
+            actor_id: 3,
+            user_id: 4,
+            prev_participant: Some(
+                tl::types::ChannelParticipant {
+                    user_id: 4,
+                    date: 2,
+                }
+                .into(),
+            ),
+            new_participant: Some(
+                tl::types::ChannelParticipantLeft {
+                    peer: tl::types::PeerUser { user_id: 4 }.into(),
+                }
+                .into(),
+            ),
+            invite: None,
+            qts: 5,
+        };
+
+        let member = ChatMemberUpdate::new(update.into(), &ChatMap::empty()).unwrap();
+        assert_eq!(member.user_id(), 4);
+        assert!(matches!(member.old_participant(), Some(Role::User(_))));
+        assert!(matches!(member.new_participant(), Some(Role::Left(_))));
+    }
+
+    #[test]
+    fn other_updates_are_not_chat_member_updates() {
+        let update = tl::types::UpdateConfig {}.into();
+        assert!(ChatMemberUpdate::new(update, &ChatMap::empty()).is_none());
+    }
+}
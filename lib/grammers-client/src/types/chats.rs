@@ -41,7 +41,7 @@ impl AdminRightsBuilderInner {
                 .invoke(&tl::functions::channels::EditAdmin {
                     channel: chan,
                     user_id: self.user.clone(),
-                    admin_rights: tl::enums::ChatAdminRights::Rights(self.rights.clone()),
+                    admin_rights: tl::enums::ChatAdminRights::Rights(self.rights),
                     rank: self.rank.clone(),
                 })
                 .await
@@ -317,7 +317,7 @@ impl BannedRightsBuilderInner {
                 .invoke(&tl::functions::channels::EditBanned {
                     channel: chan,
                     participant: self.peer.clone(),
-                    banned_rights: tl::enums::ChatBannedRights::Rights(self.rights.clone()),
+                    banned_rights: tl::enums::ChatBannedRights::Rights(self.rights),
                 })
                 .await
                 .map(drop)
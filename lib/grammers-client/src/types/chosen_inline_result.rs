@@ -0,0 +1,74 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{Chat, ChatMap, User};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// Represents the result of an inline query chosen by a user and sent to their chat partner.
+///
+/// This update only occurs for bots which have inline feedback enabled through @BotFather.
+#[derive(Clone)]
+pub struct ChosenInlineResult {
+    raw: tl::types::UpdateBotInlineSend,
+    chats: Arc<ChatMap>,
+}
+
+impl ChosenInlineResult {
+    pub(crate) fn new(raw: tl::types::UpdateBotInlineSend, chats: &Arc<ChatMap>) -> Self {
+        Self {
+            raw,
+            chats: chats.clone(),
+        }
+    }
+
+    /// The identifier of the result that was chosen, as given when answering the inline query.
+    pub fn result_id(&self) -> &str {
+        self.raw.id.as_str()
+    }
+
+    /// User that chose the result.
+    pub fn from(&self) -> &User {
+        match self
+            .chats
+            .get(
+                &tl::types::PeerUser {
+                    user_id: self.raw.user_id,
+                }
+                .into(),
+            )
+            .unwrap()
+        {
+            Chat::User(user) => user,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The text of the inline query that was used to obtain the result.
+    pub fn query(&self) -> &str {
+        self.raw.query.as_str()
+    }
+
+    /// The identifier of the sent inline message.
+    ///
+    /// This is only present if the result had an inline keyboard attached, and can be used to
+    /// edit the message later.
+    pub fn inline_message_id(&self) -> Option<&tl::enums::InputBotInlineMessageId> {
+        self.raw.msg_id.as_ref()
+    }
+}
+
+impl fmt::Debug for ChosenInlineResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChosenInlineResult")
+            .field("result_id", &self.result_id())
+            .field("query", &self.query())
+            .field("from", &self.from())
+            .finish()
+    }
+}
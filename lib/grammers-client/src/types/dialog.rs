@@ -42,4 +42,89 @@ impl Dialog {
     pub fn chat(&self) -> &Chat {
         &self.chat
     }
+
+    /// The last message sent in this dialog, if it's known.
+    pub fn top_message(&self) -> Option<&Message> {
+        self.last_message.as_ref()
+    }
+
+    /// The amount of unread messages in this dialog.
+    ///
+    /// For folders, this is the amount of unread messages in all the dialogs inside it.
+    pub fn unread_count(&self) -> i32 {
+        match &self.dialog {
+            tl::enums::Dialog::Dialog(dialog) => dialog.unread_count,
+            tl::enums::Dialog::Folder(folder) => {
+                folder.unread_muted_messages_count + folder.unread_unmuted_messages_count
+            }
+        }
+    }
+
+    /// Whether this dialog is pinned to the top of the dialog list.
+    pub fn is_pinned(&self) -> bool {
+        match &self.dialog {
+            tl::enums::Dialog::Dialog(dialog) => dialog.pinned,
+            tl::enums::Dialog::Folder(folder) => folder.pinned,
+        }
+    }
+
+    /// The identifier of the folder this dialog belongs to, if any.
+    ///
+    /// For folders, this is the identifier of the folder itself.
+    pub fn folder_id(&self) -> Option<i32> {
+        match &self.dialog {
+            tl::enums::Dialog::Dialog(dialog) => dialog.folder_id,
+            tl::enums::Dialog::Folder(folder) => {
+                let tl::enums::Folder::Folder(folder) = &folder.folder;
+                Some(folder.id)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dialog_fields_are_exposed() {
+        let raw = tl::types::Dialog {
+            pinned: true,
+            unread_mark: false,
+            view_forum_as_messages: false,
+            peer: tl::types::PeerUser { user_id: 1 }.into(),
+            top_message: 10,
+            read_inbox_max_id: 8,
+            read_outbox_max_id: 9,
+            unread_count: 2,
+            unread_mentions_count: 0,
+            unread_reactions_count: 0,
+            notify_settings: tl::types::PeerNotifySettings {
+                show_previews: None,
+                silent: None,
+                mute_until: None,
+                ios_sound: None,
+                android_sound: None,
+                other_sound: None,
+                stories_muted: None,
+                stories_hide_sender: None,
+                stories_ios_sound: None,
+                stories_android_sound: None,
+                stories_other_sound: None,
+            }
+            .into(),
+            pts: None,
+            draft: None,
+            folder_id: Some(1),
+            ttl_period: None,
+        };
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 1 }.into()], Vec::new());
+
+        let dialog = Dialog::new(raw.into(), &mut HashMap::new(), &chats);
+        assert_eq!(dialog.chat().id(), 1);
+        assert!(dialog.top_message().is_none());
+        assert_eq!(dialog.unread_count(), 2);
+        assert!(dialog.is_pinned());
+        assert_eq!(dialog.folder_id(), Some(1));
+    }
 }
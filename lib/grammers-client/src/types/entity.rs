@@ -0,0 +1,166 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use grammers_tl_types as tl;
+use std::ops::Range;
+
+/// The kind of formatting applied by a message entity to a part of the message text.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EntityKind {
+    /// An entity of a kind not known to the library.
+    Unknown,
+    /// A `@username` mention.
+    Mention,
+    /// A `#hashtag`.
+    Hashtag,
+    /// A `/command` directed to bots.
+    BotCommand,
+    /// A URL displayed as-is.
+    Url,
+    /// An email address.
+    Email,
+    /// Bold text.
+    Bold,
+    /// Italic text.
+    Italic,
+    /// Inline monospace text.
+    Code,
+    /// A preformatted code block, optionally tagged with its programming language.
+    Pre { language: String },
+    /// Text which links to the given URL when clicked.
+    TextUrl { url: String },
+    /// Text which mentions the given user, even if they have no username.
+    MentionName { user_id: i64 },
+    /// A phone number.
+    Phone,
+    /// A `$CASHTAG`.
+    Cashtag,
+    /// Underlined text.
+    Underline,
+    /// Strikethrough text.
+    Strike,
+    /// A bank card number.
+    BankCard,
+    /// Text hidden behind a spoiler.
+    Spoiler,
+    /// Text replaced by the given custom emoji document.
+    CustomEmoji { document_id: i64 },
+    /// A quote block.
+    Blockquote,
+}
+
+impl EntityKind {
+    pub(crate) fn from_raw(entity: &tl::enums::MessageEntity) -> Self {
+        use tl::enums::MessageEntity as E;
+
+        match entity {
+            E::Unknown(_) => Self::Unknown,
+            E::Mention(_) => Self::Mention,
+            E::Hashtag(_) => Self::Hashtag,
+            E::BotCommand(_) => Self::BotCommand,
+            E::Url(_) => Self::Url,
+            E::Email(_) => Self::Email,
+            E::Bold(_) => Self::Bold,
+            E::Italic(_) => Self::Italic,
+            E::Code(_) => Self::Code,
+            E::Pre(e) => Self::Pre {
+                language: e.language.clone(),
+            },
+            E::TextUrl(e) => Self::TextUrl { url: e.url.clone() },
+            E::MentionName(e) => Self::MentionName { user_id: e.user_id },
+            E::InputMessageEntityMentionName(e) => match &e.user_id {
+                tl::enums::InputUser::User(u) => Self::MentionName { user_id: u.user_id },
+                _ => Self::Unknown,
+            },
+            E::Phone(_) => Self::Phone,
+            E::Cashtag(_) => Self::Cashtag,
+            E::Underline(_) => Self::Underline,
+            E::Strike(_) => Self::Strike,
+            E::BankCard(_) => Self::BankCard,
+            E::Spoiler(_) => Self::Spoiler,
+            E::CustomEmoji(e) => Self::CustomEmoji {
+                document_id: e.document_id,
+            },
+            E::Blockquote(_) => Self::Blockquote,
+            // Entities added in later layers are unknown until they get a kind of their own.
+            #[allow(unreachable_patterns)]
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Converts the UTF-16 based `offset` and `length` Telegram uses into a byte range of `text`.
+///
+/// Both ends are clamped to the text, and positions falling in the middle of a surrogate pair
+/// are moved past the character so that the range can always be used to slice `text`.
+pub(crate) fn utf16_range_to_bytes(text: &str, offset: i32, length: i32) -> Range<usize> {
+    let start = offset.max(0) as usize;
+    let end = start + length.max(0) as usize;
+
+    let mut range = text.len()..text.len();
+    let mut utf16_pos = 0;
+    let mut found_start = false;
+    for (byte_pos, c) in text.char_indices() {
+        if !found_start && utf16_pos >= start {
+            range.start = byte_pos;
+            found_start = true;
+        }
+        if utf16_pos >= end {
+            range.end = byte_pos;
+            break;
+        }
+        utf16_pos += c.len_utf16();
+    }
+
+    range.end = range.end.max(range.start);
+    range
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_offsets_match_bytes() {
+        let text = "hello world";
+        assert_eq!(utf16_range_to_bytes(text, 6, 5), 6..11);
+        assert_eq!(&text[utf16_range_to_bytes(text, 0, 5)], "hello");
+    }
+
+    #[test]
+    fn surrogate_pairs_count_twice() {
+        let text = "🦀 ferris ñ";
+        let range = utf16_range_to_bytes(text, 3, 6);
+        assert_eq!(&text[range], "ferris");
+        let range = utf16_range_to_bytes(text, 10, 1);
+        assert_eq!(&text[range], "ñ");
+    }
+
+    #[test]
+    fn out_of_bounds_is_clamped() {
+        let text = "abc";
+        assert_eq!(utf16_range_to_bytes(text, 1, 10), 1..3);
+        assert_eq!(utf16_range_to_bytes(text, 5, 2), 3..3);
+    }
+
+    #[test]
+    fn raw_entities_become_kinds() {
+        let entity = tl::types::MessageEntityTextUrl {
+            offset: 0,
+            length: 1,
+            url: "https://example.com".into(),
+        }
+        .into();
+        assert_eq!(
+            EntityKind::from_raw(&entity),
+            EntityKind::TextUrl {
+                url: "https://example.com".into()
+            }
+        );
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{Channel, User};
+
+/// Where a forwarded message originally came from.
+#[derive(Clone, Debug)]
+pub enum ForwardOrigin {
+    /// The message was originally sent by this user.
+    User(User),
+    /// The message was originally posted in this channel.
+    ///
+    /// The identifier of the original post is only known when Telegram includes it.
+    Channel {
+        channel: Channel,
+        message_id: Option<i32>,
+    },
+    /// The message was originally sent by a user who hides their account when their messages
+    /// are forwarded, so only their name is known.
+    HiddenUser { name: String },
+}
@@ -0,0 +1,212 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use crate::Client;
+use grammers_mtsender::InvocationError;
+use grammers_session::PackedChat;
+use grammers_tl_types as tl;
+use std::fmt;
+
+/// The inline keyboard attached to a message, usually sent by bots.
+///
+/// The buttons are laid out in rows, and can be clicked with [`InlineKeyboard::click`].
+#[derive(Clone)]
+pub struct InlineKeyboard {
+    rows: Vec<Vec<InlineButton>>,
+    chat: PackedChat,
+    msg_id: i32,
+    client: Client,
+}
+
+/// A single button inside an [`InlineKeyboard`].
+#[derive(Clone, Debug)]
+pub struct InlineButton {
+    text: String,
+    raw: tl::enums::KeyboardButton,
+}
+
+impl InlineKeyboard {
+    pub(crate) fn new(
+        markup: tl::types::ReplyInlineMarkup,
+        chat: PackedChat,
+        msg_id: i32,
+        client: Client,
+    ) -> Self {
+        Self {
+            rows: markup
+                .rows
+                .into_iter()
+                .map(|tl::enums::KeyboardButtonRow::Row(row)| {
+                    row.buttons
+                        .into_iter()
+                        .map(InlineButton::from_raw)
+                        .collect()
+                })
+                .collect(),
+            chat,
+            msg_id,
+            client,
+        }
+    }
+
+    /// The rows of buttons in this keyboard, from top to bottom.
+    pub fn rows(&self) -> &[Vec<InlineButton>] {
+        &self.rows
+    }
+
+    /// The button at the given row and column, if there is one.
+    pub fn button(&self, row: usize, col: usize) -> Option<&InlineButton> {
+        self.rows.get(row).and_then(|r| r.get(col))
+    }
+
+    /// Click the button at the given row and column, and return the bot's answer to it.
+    ///
+    /// Only buttons with [`callback_data`](InlineButton::callback_data) can be clicked this way.
+    /// `None` is returned without contacting Telegram if there is no such button at the given
+    /// position. Buttons which require the account's password are not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(message: grammers_client::types::Message) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(keyboard) = message.buttons() {
+    ///     if let Some(answer) = keyboard.click(0, 0).await? {
+    ///         println!("the bot answered: {:?}", answer.message);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn click(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Result<Option<tl::types::messages::BotCallbackAnswer>, InvocationError> {
+        let data = match self.button(row, col).and_then(InlineButton::callback_data) {
+            Some(data) => data.to_vec(),
+            None => return Ok(None),
+        };
+
+        let tl::enums::messages::BotCallbackAnswer::Answer(answer) = self
+            .client
+            .invoke(&tl::functions::messages::GetBotCallbackAnswer {
+                game: false,
+                peer: self.chat.to_input_peer(),
+                msg_id: self.msg_id,
+                data: Some(data),
+                password: None,
+            })
+            .await?;
+
+        Ok(Some(answer))
+    }
+}
+
+impl fmt::Debug for InlineKeyboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InlineKeyboard")
+            .field("rows", &self.rows)
+            .field("chat", &self.chat)
+            .field("msg_id", &self.msg_id)
+            .finish()
+    }
+}
+
+impl InlineButton {
+    pub(crate) fn from_raw(raw: tl::enums::KeyboardButton) -> Self {
+        Self {
+            text: raw.text(),
+            raw,
+        }
+    }
+
+    /// The text displayed on the button.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The data sent to the bot when the button is clicked, if it is a callback button.
+    pub fn callback_data(&self) -> Option<&[u8]> {
+        match &self.raw {
+            tl::enums::KeyboardButton::Callback(b) => Some(&b.data),
+            _ => None,
+        }
+    }
+
+    /// The URL opened when the button is clicked, if it is a link button.
+    pub fn url(&self) -> Option<&str> {
+        use tl::enums::KeyboardButton as B;
+
+        match &self.raw {
+            B::Url(b) => Some(&b.url),
+            B::UrlAuth(b) => Some(&b.url),
+            B::InputKeyboardButtonUrlAuth(b) => Some(&b.url),
+            B::WebView(b) => Some(&b.url),
+            B::SimpleWebView(b) => Some(&b.url),
+            _ => None,
+        }
+    }
+
+    /// The inline query inserted in the input field when the button is clicked, if it is a
+    /// button to switch to inline mode.
+    pub fn switch_inline_query(&self) -> Option<&str> {
+        match &self.raw {
+            tl::enums::KeyboardButton::SwitchInline(b) => Some(&b.query),
+            _ => None,
+        }
+    }
+
+    /// The raw button, as sent by Telegram.
+    pub fn raw(&self) -> &tl::enums::KeyboardButton {
+        &self.raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callback_button_exposes_data() {
+        let button = InlineButton::from_raw(
+            tl::types::KeyboardButtonCallback {
+                requires_password: false,
+                text: "click".into(),
+                data: vec![1, 2, 3],
+            }
+            .into(),
+        );
+        assert_eq!(button.text(), "click");
+        assert_eq!(button.callback_data(), Some(&[1, 2, 3][..]));
+        assert_eq!(button.url(), None);
+        assert_eq!(button.switch_inline_query(), None);
+    }
+
+    #[test]
+    fn url_and_switch_inline_buttons() {
+        let button = InlineButton::from_raw(
+            tl::types::KeyboardButtonUrl {
+                text: "open".into(),
+                url: "https://example.com".into(),
+            }
+            .into(),
+        );
+        assert_eq!(button.url(), Some("https://example.com"));
+        assert_eq!(button.callback_data(), None);
+
+        let button = InlineButton::from_raw(
+            tl::types::KeyboardButtonSwitchInline {
+                same_peer: true,
+                text: "search".into(),
+                query: "cats".into(),
+                peer_types: None,
+            }
+            .into(),
+        );
+        assert_eq!(button.switch_inline_query(), Some("cats"));
+    }
+}
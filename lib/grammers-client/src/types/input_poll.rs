@@ -0,0 +1,161 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use grammers_tl_types as tl;
+use std::time::Duration;
+
+/// Construct a poll to be sent with [`Client::send_poll`](crate::Client::send_poll).
+///
+/// # Examples
+///
+/// ```
+/// use grammers_client::types::InputPoll;
+/// use std::time::Duration;
+///
+/// let poll = InputPoll::new("What is 2 + 2?", &["3", "4", "5"])
+///     .quiz(1, Some("Count on your fingers"))
+///     .close_period(Duration::from_secs(60));
+/// ```
+#[derive(Clone, Debug)]
+pub struct InputPoll {
+    question: String,
+    answers: Vec<String>,
+    multiple_choice: bool,
+    public_voters: bool,
+    quiz: Option<(usize, Option<String>)>,
+    close_period: Option<Duration>,
+}
+
+impl InputPoll {
+    /// Creates a new poll with the given question and answer options.
+    pub fn new(question: &str, options: &[&str]) -> Self {
+        Self {
+            question: question.to_string(),
+            answers: options.iter().map(|o| o.to_string()).collect(),
+            multiple_choice: false,
+            public_voters: false,
+            quiz: None,
+            close_period: None,
+        }
+    }
+
+    /// Whether more than one option can be chosen. Quizzes can only have a single answer.
+    pub fn multiple_choice(mut self, multiple_choice: bool) -> Self {
+        self.multiple_choice = multiple_choice;
+        self
+    }
+
+    /// Whether the votes are visible to everyone. Not all chats support public polls.
+    pub fn public_voters(mut self, public_voters: bool) -> Self {
+        self.public_voters = public_voters;
+        self
+    }
+
+    /// Turns the poll into a quiz, where `correct_option` is the index of the correct answer.
+    ///
+    /// The `explanation` is shown to those who choose the wrong answer.
+    pub fn quiz(mut self, correct_option: usize, explanation: Option<&str>) -> Self {
+        self.quiz = Some((correct_option, explanation.map(str::to_string)));
+        self
+    }
+
+    /// Close the poll automatically after the given duration, up to 10 minutes.
+    pub fn close_period(mut self, close_period: Duration) -> Self {
+        self.close_period = Some(close_period);
+        self
+    }
+
+    pub(crate) fn into_media(self) -> tl::enums::InputMedia {
+        // The options only need to be unique within the poll.
+        let option = |i: usize| vec![i as u8];
+
+        tl::types::InputMediaPoll {
+            poll: tl::types::Poll {
+                id: 0,
+                closed: false,
+                public_voters: self.public_voters,
+                multiple_choice: self.multiple_choice,
+                quiz: self.quiz.is_some(),
+                question: tl::types::TextWithEntities {
+                    text: self.question,
+                    entities: Vec::new(),
+                }
+                .into(),
+                answers: self
+                    .answers
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, text)| {
+                        tl::types::PollAnswer {
+                            text: tl::types::TextWithEntities {
+                                text,
+                                entities: Vec::new(),
+                            }
+                            .into(),
+                            option: option(i),
+                        }
+                        .into()
+                    })
+                    .collect(),
+                close_period: self.close_period.map(|d| d.as_secs() as i32),
+                close_date: None,
+            }
+            .into(),
+            correct_answers: self
+                .quiz
+                .as_ref()
+                .map(|(correct, _)| vec![option(*correct)]),
+            solution_entities: self
+                .quiz
+                .as_ref()
+                .and_then(|(_, explanation)| explanation.as_ref())
+                .map(|_| Vec::new()),
+            solution: self.quiz.and_then(|(_, explanation)| explanation),
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_poll(poll: InputPoll) -> (tl::types::InputMediaPoll, tl::types::Poll) {
+        match poll.into_media() {
+            tl::enums::InputMedia::Poll(media) => {
+                let tl::enums::Poll::Poll(poll) = media.poll.clone();
+                (media, poll)
+            }
+            _ => panic!("poll did not become an input poll"),
+        }
+    }
+
+    #[test]
+    fn regular_poll() {
+        let (media, poll) = input_poll(InputPoll::new("Pizza?", &["Yes", "No"]));
+        assert!(!poll.quiz);
+        assert_eq!(poll.answers.len(), 2);
+        assert_eq!(poll.close_period, None);
+        assert_eq!(media.correct_answers, None);
+        assert_eq!(media.solution, None);
+    }
+
+    #[test]
+    fn quiz_poll() {
+        let (media, poll) = input_poll(
+            InputPoll::new("2 + 2?", &["3", "4"])
+                .quiz(1, Some("Count"))
+                .close_period(Duration::from_secs(30)),
+        );
+        assert!(poll.quiz);
+        assert_eq!(poll.close_period, Some(30));
+        let tl::enums::PollAnswer::Answer(answer) = &poll.answers[1];
+        assert_eq!(media.correct_answers, Some(vec![answer.option.clone()]));
+        assert_eq!(media.solution.as_deref(), Some("Count"));
+        assert_eq!(media.solution_entities, Some(Vec::new()));
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{ChatMap, User};
+use crate::utils;
+use chrono::{DateTime, Utc};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// An invite link to a group or channel, created by one of its administrators.
+#[derive(Clone)]
+pub struct InviteLink {
+    raw: tl::types::ChatInviteExported,
+    chats: Arc<ChatMap>,
+}
+
+impl InviteLink {
+    pub(crate) fn new(raw: tl::types::ChatInviteExported, chats: &Arc<ChatMap>) -> Self {
+        Self {
+            raw,
+            chats: chats.clone(),
+        }
+    }
+
+    /// Returns `None` for the pseudo-link used to ask to join public chats.
+    pub(crate) fn from_raw(
+        raw: tl::enums::ExportedChatInvite,
+        chats: &Arc<ChatMap>,
+    ) -> Option<Self> {
+        match raw {
+            tl::enums::ExportedChatInvite::ChatInviteExported(invite) => {
+                Some(Self::new(invite, chats))
+            }
+            tl::enums::ExportedChatInvite::ChatInvitePublicJoinRequests => None,
+        }
+    }
+
+    /// The link itself, such as `https://t.me/+abc`.
+    pub fn link(&self) -> &str {
+        &self.raw.link
+    }
+
+    /// The optional title given to the link, only visible to administrators.
+    pub fn title(&self) -> Option<&str> {
+        self.raw.title.as_deref()
+    }
+
+    /// The identifier of the administrator who created the link.
+    pub fn creator_id(&self) -> i64 {
+        self.raw.admin_id
+    }
+
+    /// The administrator who created the link, if it's known.
+    pub fn creator(&self) -> Option<&User> {
+        self.chats.get_user(self.raw.admin_id)
+    }
+
+    /// When the link was created.
+    pub fn date(&self) -> DateTime<Utc> {
+        utils::date(self.raw.date)
+    }
+
+    /// How many users can join through this link, if it's limited.
+    pub fn usage_limit(&self) -> Option<i32> {
+        self.raw.usage_limit
+    }
+
+    /// When the link stops working, if it expires.
+    pub fn expire_date(&self) -> Option<DateTime<Utc>> {
+        self.raw.expire_date.map(utils::date)
+    }
+
+    /// How many users have joined through this link.
+    pub fn usage_count(&self) -> i32 {
+        self.raw.usage.unwrap_or(0)
+    }
+
+    /// Whether this is the chat's primary link, which never expires. Revoking it generates a
+    /// new primary link.
+    pub fn is_permanent(&self) -> bool {
+        self.raw.permanent
+    }
+
+    /// Whether this link has been revoked and can no longer be used.
+    pub fn is_revoked(&self) -> bool {
+        self.raw.revoked
+    }
+
+    /// Whether users joining through this link must first be approved by an administrator.
+    pub fn request_needed(&self) -> bool {
+        self.raw.request_needed
+    }
+
+    /// The raw invite as sent by Telegram.
+    pub fn raw(&self) -> &tl::types::ChatInviteExported {
+        &self.raw
+    }
+}
+
+impl fmt::Debug for InviteLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InviteLink")
+            .field("link", &self.link())
+            .field("creator_id", &self.creator_id())
+            .field("usage_limit", &self.usage_limit())
+            .field("expire_date", &self.expire_date())
+            .field("usage_count", &self.usage_count())
+            .field("is_permanent", &self.is_permanent())
+            .field("is_revoked", &self.is_revoked())
+            .finish()
+    }
+}
+
+/// Options for the invite link created by [`Client::create_invite_link`](crate::Client::create_invite_link).
+///
+/// # Examples
+///
+/// ```
+/// use grammers_client::types::InviteLinkOptions;
+///
+/// let options = InviteLinkOptions::new()
+///     .title("For friends")
+///     .usage_limit(10);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InviteLinkOptions {
+    expire_date: Option<DateTime<Utc>>,
+    usage_limit: Option<i32>,
+    request_needed: bool,
+    title: Option<String>,
+}
+
+impl InviteLinkOptions {
+    /// Creates options for a link which never expires and can be used any number of times.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make the link stop working at the given date.
+    pub fn expire_date(mut self, expire_date: DateTime<Utc>) -> Self {
+        self.expire_date = Some(expire_date);
+        self
+    }
+
+    /// Make the link stop working once this many users have joined through it.
+    ///
+    /// Cannot be combined with [`InviteLinkOptions::request_needed`].
+    pub fn usage_limit(mut self, usage_limit: i32) -> Self {
+        self.usage_limit = Some(usage_limit);
+        self
+    }
+
+    /// Whether users joining through this link must first be approved by an administrator.
+    pub fn request_needed(mut self, request_needed: bool) -> Self {
+        self.request_needed = request_needed;
+        self
+    }
+
+    /// Give the link a title, only visible to administrators.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub(crate) fn into_request(
+        self,
+        peer: tl::enums::InputPeer,
+    ) -> tl::functions::messages::ExportChatInvite {
+        tl::functions::messages::ExportChatInvite {
+            legacy_revoke_permanent: false,
+            request_needed: self.request_needed,
+            peer,
+            expire_date: self.expire_date.map(|date| date.timestamp() as i32),
+            usage_limit: self.usage_limit,
+            title: self.title,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invite() -> tl::types::ChatInviteExported {
+        tl::types::ChatInviteExported {
+            revoked: false,
+            permanent: true,
+            request_needed: false,
+            link: "https://t.me/+abc".to_string(),
+            admin_id: 2,
+            date: 1,
+            start_date: None,
+            expire_date: Some(3),
+            usage_limit: Some(10),
+            usage: None,
+            requested: None,
+            title: None,
+        }
+    }
+
+    #[test]
+    fn invite_link_fields() {
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 2 }.into()], Vec::new());
+        let link = InviteLink::new(invite(), &chats);
+
+        assert_eq!(link.link(), "https://t.me/+abc");
+        assert_eq!(link.creator().map(|u| u.id()), Some(2));
+        assert_eq!(link.usage_limit(), Some(10));
+        assert_eq!(link.expire_date().map(|d| d.timestamp()), Some(3));
+        assert_eq!(link.usage_count(), 0);
+        assert!(link.is_permanent());
+        assert!(!link.is_revoked());
+    }
+
+    #[test]
+    fn public_join_requests_are_not_links() {
+        assert!(InviteLink::from_raw(
+            tl::enums::ExportedChatInvite::ChatInvitePublicJoinRequests,
+            &ChatMap::empty()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn options_into_request() {
+        let request = InviteLinkOptions::new()
+            .title("Friends")
+            .usage_limit(5)
+            .into_request(tl::enums::InputPeer::PeerSelf);
+
+        assert_eq!(request.title.as_deref(), Some("Friends"));
+        assert_eq!(request.usage_limit, Some(5));
+        assert_eq!(request.expire_date, None);
+        assert!(!request.request_needed);
+    }
+}
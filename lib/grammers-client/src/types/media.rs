@@ -6,10 +6,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 use crate::types::photo_sizes::{PhotoSize, VecExt};
-use crate::utils::Date;
+use crate::types::Downloadable;
+use crate::utils::{self, Date};
 use crate::Client;
 use grammers_tl_types as tl;
 use std::fmt::Debug;
+use std::io;
+use std::path::Path;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Photo {
@@ -146,23 +149,27 @@ impl Photo {
     }
 
     fn to_input_media(&self) -> tl::types::InputMediaPhoto {
+        tl::types::InputMediaPhoto {
+            spoiler: false,
+            id: self.to_input_photo(),
+            ttl_seconds: self.photo.ttl_seconds,
+        }
+    }
+
+    pub(crate) fn to_input_photo(&self) -> tl::enums::InputPhoto {
         use tl::{
             enums::{InputPhoto as eInputPhoto, Photo},
             types::InputPhoto,
         };
 
-        tl::types::InputMediaPhoto {
-            spoiler: false,
-            id: match self.photo.photo {
-                Some(Photo::Photo(ref photo)) => InputPhoto {
-                    id: photo.id,
-                    access_hash: photo.access_hash,
-                    file_reference: photo.file_reference.clone(),
-                }
-                .into(),
-                _ => eInputPhoto::Empty,
-            },
-            ttl_seconds: self.photo.ttl_seconds,
+        match self.photo.photo {
+            Some(Photo::Photo(ref photo)) => InputPhoto {
+                id: photo.id,
+                access_hash: photo.access_hash,
+                file_reference: photo.file_reference.clone(),
+            }
+            .into(),
+            _ => eInputPhoto::Empty,
         }
     }
 
@@ -203,10 +210,27 @@ impl Photo {
         }
     }
 
+    /// The date on which the photo was uploaded, if any.
+    pub fn date(&self) -> Option<Date> {
+        match self.photo.photo.as_ref() {
+            Some(tl::enums::Photo::Photo(photo)) => Some(utils::date(photo.date)),
+            _ => None,
+        }
+    }
+
     /// Returns true if the photo is a spoiler.
     pub fn is_spoiler(&self) -> bool {
         self.photo.spoiler
     }
+
+    /// Downloads the photo into the specified path.
+    ///
+    /// This is the same as using [`Client::download_media`] with the photo.
+    pub async fn download<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        self.client
+            .download_media(&Downloadable::Media(Media::Photo(self.clone())), path)
+            .await
+    }
 }
 
 impl Document {
@@ -535,6 +559,36 @@ impl Poll {
         }
     }
 
+    /// Input media that closes the poll with the given identifier once it is edited in.
+    pub(crate) fn closing_input_media(poll_id: i64) -> tl::types::InputMediaPoll {
+        // The server only looks at the identifier and the flag, same as the official clients.
+        tl::types::InputMediaPoll {
+            poll: tl::types::Poll {
+                id: poll_id,
+                closed: true,
+                public_voters: false,
+                multiple_choice: false,
+                quiz: false,
+                question: tl::types::TextWithEntities {
+                    text: String::new(),
+                    entities: Vec::new(),
+                }
+                .into(),
+                answers: Vec::new(),
+                close_period: None,
+                close_date: None,
+            }
+            .into(),
+            correct_answers: None,
+            solution: None,
+            solution_entities: None,
+        }
+    }
+
+    pub(crate) fn id(&self) -> i64 {
+        self.poll.id
+    }
+
     /// Return question of the poll
     pub fn question(&self) -> &grammers_tl_types::enums::TextWithEntities {
         &self.poll.question
@@ -564,6 +618,11 @@ impl Poll {
         self.results.total_voters
     }
 
+    /// Return the raw results of the poll, which are final once the poll is [`closed`](Self::closed).
+    pub fn results(&self) -> &tl::types::PollResults {
+        &self.results
+    }
+
     /// Return details of the voters choices:
     /// how much voters chose each answer and wether current option
     pub fn iter_voters_summary(
@@ -583,7 +642,7 @@ impl Geo {
 
         match &geo.geo {
             eGeoPoint::Empty => None,
-            eGeoPoint::Point(point) => Some(Self { geo: point.clone() }),
+            eGeoPoint::Point(point) => Some(Self { geo: *point }),
         }
     }
 
@@ -672,9 +731,7 @@ impl Venue {
     fn _from_media(venue: tl::types::MessageMediaVenue) -> Self {
         use tl::types::MessageMediaGeo;
         Self {
-            geo: Geo::from_media(MessageMediaGeo {
-                geo: venue.geo.clone(),
-            }),
+            geo: Geo::from_media(MessageMediaGeo { geo: venue.geo }),
             venue,
         }
     }
@@ -733,9 +790,7 @@ impl GeoLive {
     fn _from_media(geolive: tl::types::MessageMediaGeoLive) -> Self {
         use tl::types::MessageMediaGeo;
         Self {
-            geo: Geo::from_media(MessageMediaGeo {
-                geo: geolive.geo.clone(),
-            }),
+            geo: Geo::from_media(MessageMediaGeo { geo: geolive.geo }),
             geolive,
         }
     }
@@ -7,7 +7,8 @@
 // except according to those terms.
 #[cfg(any(feature = "markdown", feature = "html"))]
 use crate::parsers;
-use crate::types::{Downloadable, InputMessage, Media, Photo};
+use crate::types::entity::utf16_range_to_bytes;
+use crate::types::{Downloadable, EntityKind, InputMessage, Media, Photo};
 use crate::utils;
 use crate::ChatMap;
 use crate::{types, Client};
@@ -16,7 +17,9 @@ use grammers_mtsender::InvocationError;
 use grammers_session::PackedChat;
 use grammers_tl_types as tl;
 use std::fmt;
+use std::future::Future;
 use std::io;
+use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
 use types::Chat;
@@ -270,11 +273,47 @@ impl Message {
         self.msg.fwd_from.clone()
     }
 
+    /// Whether this message was forwarded from a previous message.
+    pub fn is_forwarded(&self) -> bool {
+        self.msg.fwd_from.is_some()
+    }
+
+    /// If this message was forwarded from a previous message, return where it originally came
+    /// from.
+    ///
+    /// `None` is also returned if the origin is neither a user nor a channel, for example, when
+    /// the message was originally sent by an anonymous group administrator.
+    pub fn forward_from(&self) -> Option<types::ForwardOrigin> {
+        let tl::enums::MessageFwdHeader::Header(header) = self.msg.fwd_from.as_ref()?;
+        match &header.from_id {
+            Some(from) => match utils::always_find_entity(from, &self.chats, &self.client) {
+                Chat::User(user) => Some(types::ForwardOrigin::User(user)),
+                Chat::Channel(channel) => Some(types::ForwardOrigin::Channel {
+                    channel,
+                    message_id: header.channel_post,
+                }),
+                Chat::Group(_) => None,
+            },
+            None => header
+                .from_name
+                .clone()
+                .map(|name| types::ForwardOrigin::HiddenUser { name }),
+        }
+    }
+
     /// If this message was sent @via some inline bot, return the bot's user identifier.
     pub fn via_bot_id(&self) -> Option<i64> {
         self.msg.via_bot_id
     }
 
+    /// If this message was sent @via some inline bot, return the bot's user.
+    ///
+    /// Unlike [`via_bot_id`](Self::via_bot_id), this returns `None` if the bot's user is not
+    /// known, because Telegram did not include it alongside the message.
+    pub fn via_bot(&self) -> Option<&types::User> {
+        via_bot(self.msg.via_bot_id, &self.chats)
+    }
+
     /// If this message is replying to a previous message, return the header with information
     /// about that reply.
     pub fn reply_header(&self) -> Option<tl::enums::MessageReplyHeader> {
@@ -295,7 +334,7 @@ impl Message {
         &self.msg.message
     }
 
-    /// Like [`text`](Self::text), but with the [`fmt_entities`](Self::fmt_entities)
+    /// Like [`text`](Self::text), but with the [`entities`](Self::entities)
     /// applied to produce a markdown string instead.
     ///
     /// Some formatting entities automatically added by Telegram, such as bot commands or
@@ -313,7 +352,7 @@ impl Message {
         }
     }
 
-    /// Like [`text`](Self::text), but with the [`fmt_entities`](Self::fmt_entities)
+    /// Like [`text`](Self::text), but with the [`entities`](Self::entities)
     /// applied to produce a HTML string instead.
     ///
     /// Some formatting entities automatically added by Telegram, such as bot commands or
@@ -345,11 +384,55 @@ impl Message {
         self.msg.reply_markup.clone()
     }
 
-    /// The formatting entities used to format this message, such as bold, italic, with their
-    /// offsets and lengths.
-    pub fn fmt_entities(&self) -> Option<&Vec<tl::enums::MessageEntity>> {
-        // TODO correct the offsets and lengths to match the byte offsets
-        self.msg.entities.as_ref()
+    /// The inline keyboard attached to this message, if it has one.
+    ///
+    /// Only inline keyboards (whose buttons are shown below the message) are returned, since
+    /// the buttons of other reply markups are not associated with a specific message.
+    pub fn buttons(&self) -> Option<types::InlineKeyboard> {
+        match &self.msg.reply_markup {
+            Some(tl::enums::ReplyMarkup::ReplyInlineMarkup(markup)) => {
+                Some(types::InlineKeyboard::new(
+                    markup.clone(),
+                    self.chat().pack(),
+                    self.msg.id,
+                    self.client.clone(),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// The raw formatting entities used to format this message, such as bold or italic.
+    ///
+    /// Their offsets and lengths are measured in UTF-16 code units, as Telegram does.
+    /// Use [`fmt_entities`](Self::fmt_entities) to get ranges usable with [`text`](Self::text).
+    pub fn entities(&self) -> &[tl::enums::MessageEntity] {
+        self.msg.entities.as_deref().unwrap_or(&[])
+    }
+
+    /// The formatting entities used to format this message, along with the byte range of the
+    /// [`text`](Self::text) each of them covers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn f(message: grammers_client::types::Message) {
+    /// use grammers_client::types::EntityKind;
+    ///
+    /// for (range, kind) in message.fmt_entities() {
+    ///     if kind == EntityKind::Bold {
+    ///         println!("bold text: {}", &message.text()[range]);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn fmt_entities(&self) -> impl Iterator<Item = (Range<usize>, EntityKind)> + '_ {
+        self.entities().iter().map(|entity| {
+            (
+                utf16_range_to_bytes(self.text(), entity.offset(), entity.length()),
+                EntityKind::from_raw(entity),
+            )
+        })
     }
 
     /// How many views does this message have, when applicable.
@@ -429,11 +512,7 @@ impl Message {
 
     /// If this message is replying to another message, return the replied message ID.
     pub fn reply_to_message_id(&self) -> Option<i32> {
-        if let Some(tl::enums::MessageReplyHeader::Header(m)) = &self.msg.reply_to {
-            m.reply_to_msg_id
-        } else {
-            None
-        }
+        reply_to_msg_id(self.msg.reply_to.as_ref())
     }
 
     /// Fetch the message that this message is replying to, or `None` if this message is not a
@@ -447,6 +526,31 @@ impl Message {
             .await
     }
 
+    /// Fetch the message that this message is replying to.
+    ///
+    /// Unlike [`Message::get_reply`], `None` is returned right away if this message is not a
+    /// reply to a previous message, without having to `await` anything. The returned future
+    /// resolves to `None` if the replied message can no longer be found (e.g. it was deleted).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(message: grammers_client::types::Message) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(reply) = message.reply_to_message() {
+    ///     if let Some(reply) = reply.await? {
+    ///         println!("The reply said: {}", reply.text());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reply_to_message(
+        &self,
+    ) -> Option<impl Future<Output = Result<Option<Self>, InvocationError>> + '_> {
+        self.reply_to_message_id()?;
+        Some(self.get_reply())
+    }
+
     /// Respond to this message by sending a new message in the same chat, but without directly
     /// replying to it.
     ///
@@ -469,18 +573,46 @@ impl Message {
             .await
     }
 
-    /// Forward this message to another (or the same) chat.
+    /// Forward this message to another (or the same) chat, returning the forwarded copy.
+    ///
+    /// If `silent` is `true`, the members of the chat won't be notified about the new message.
+    /// If a `schedule_date` is set, the message is scheduled to be forwarded at that time
+    /// instead (see [`InputMessage::schedule_date`]).
+    ///
+    /// `None` is returned if Telegram did not send the forwarded copy back.
     ///
     /// Shorthand for `Client::forward_messages`. If you need to forward multiple messages
     /// at once, consider using that method instead.
-    pub async fn forward_to<C: Into<PackedChat>>(&self, chat: C) -> Result<Self, InvocationError> {
-        // TODO return `Message`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(message: grammers_client::types::Message, chat: grammers_client::types::Chat) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some(forwarded) = message.forward_to(&chat, true, None).await? {
+    ///     println!("forwarded as message {}", forwarded.id());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn forward_to<C: Into<PackedChat>>(
+        &self,
+        chat: C,
+        silent: bool,
+        schedule_date: Option<DateTime<Utc>>,
+    ) -> Result<Option<Self>, InvocationError> {
+        let schedule_date = schedule_date.map(utils::schedule_date).transpose()?;
+
         // When forwarding a single message, if it fails, Telegram should respond with RPC error.
-        // If it succeeds we will have the single forwarded message present which we can unwrap.
         self.client
-            .forward_messages(chat, &[self.msg.id], &self.chat())
+            .forward_messages_with(
+                chat.into(),
+                &[self.msg.id],
+                self.chat().pack(),
+                silent,
+                schedule_date,
+            )
             .await
-            .map(|mut msgs| msgs.pop().unwrap().unwrap())
+            .map(|mut msgs| msgs.pop().flatten())
     }
 
     /// Edit this message to change its text or media.
@@ -497,8 +629,16 @@ impl Message {
     /// Shorthand for `Client::delete_messages`. If you need to delete multiple messages
     /// at once, consider using that method instead.
     pub async fn delete(&self) -> Result<(), InvocationError> {
+        self.delete_for_everyone(true).await
+    }
+
+    /// Delete this message, only deleting it for the other end too if `revoke` is `true`.
+    ///
+    /// This only makes a difference in private conversations and small group chats. Messages
+    /// in channels and supergroups are always deleted for everyone.
+    pub async fn delete_for_everyone(&self, revoke: bool) -> Result<(), InvocationError> {
         self.client
-            .delete_messages(&self.chat(), &[self.msg.id])
+            .delete_messages_with_revoke(self.chat().pack(), &[self.msg.id], revoke)
             .await
             .map(drop)
     }
@@ -528,11 +668,13 @@ impl Message {
         }
     }
 
-    /// Pin this message in the chat.
+    /// Pin this message in the chat, notifying its members about it if `notify` is `true`.
     ///
     /// Shorthand for `Client::pin_message`.
-    pub async fn pin(&self) -> Result<(), InvocationError> {
-        self.client.pin_message(&self.chat(), self.msg.id).await
+    pub async fn pin(&self, notify: bool) -> Result<(), InvocationError> {
+        self.client
+            .pin_message(&self.chat(), self.msg.id, notify)
+            .await
     }
 
     /// Unpin this message from the chat.
@@ -586,6 +728,20 @@ impl Message {
     }
 }
 
+/// The identifier of the message replied to, if the reply header refers to a message.
+fn reply_to_msg_id(reply_to: Option<&tl::enums::MessageReplyHeader>) -> Option<i32> {
+    if let Some(tl::enums::MessageReplyHeader::Header(m)) = reply_to {
+        m.reply_to_msg_id
+    } else {
+        None
+    }
+}
+
+/// The inline bot with the given identifier, if it is known.
+fn via_bot(via_bot_id: Option<i64>, chats: &ChatMap) -> Option<&types::User> {
+    via_bot_id.and_then(|user_id| chats.get_user(user_id))
+}
+
 impl fmt::Debug for Message {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Message")
@@ -608,7 +764,7 @@ impl fmt::Debug for Message {
             .field("forward_header", &self.forward_header())
             .field("reply_header", &self.reply_header())
             .field("reply_markup", &self.reply_markup())
-            .field("fmt_entities", &self.fmt_entities())
+            .field("entities", &self.entities())
             .field("view_count", &self.view_count())
             .field("forward_count", &self.forward_count())
             .field("reply_count", &self.reply_count())
@@ -620,3 +776,53 @@ impl fmt::Debug for Message {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply_header(reply_to_msg_id: Option<i32>) -> tl::enums::MessageReplyHeader {
+        tl::types::MessageReplyHeader {
+            reply_to_scheduled: false,
+            forum_topic: false,
+            quote: false,
+            reply_to_msg_id,
+            reply_to_peer_id: None,
+            reply_from: None,
+            reply_media: None,
+            reply_to_top_id: None,
+            quote_text: None,
+            quote_entities: None,
+            quote_offset: None,
+        }
+        .into()
+    }
+
+    #[test]
+    fn reply_to_message_id_is_read_from_header() {
+        assert_eq!(reply_to_msg_id(Some(&reply_header(Some(7)))), Some(7));
+    }
+
+    #[test]
+    fn no_reply_to_message_id_without_replied_message() {
+        assert_eq!(reply_to_msg_id(None), None);
+        assert_eq!(reply_to_msg_id(Some(&reply_header(None))), None);
+
+        let story = tl::types::MessageReplyStoryHeader {
+            peer: tl::types::PeerUser { user_id: 1 }.into(),
+            story_id: 2,
+        }
+        .into();
+        assert_eq!(reply_to_msg_id(Some(&story)), None);
+    }
+
+    #[test]
+    fn via_bot_agrees_with_via_bot_id() {
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 7 }.into()], Vec::new());
+
+        let bot = via_bot(Some(7), &chats).expect("bot should be known");
+        assert_eq!(bot.id(), 7);
+        assert!(via_bot(Some(8), &chats).is_none());
+        assert!(via_bot(None, &chats).is_none());
+    }
+}
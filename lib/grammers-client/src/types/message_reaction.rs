@@ -0,0 +1,217 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{Chat, ChatMap};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// Occurs whenever the reactions to a message change.
+///
+/// Bots receive this update both when someone changes their own reactions, in which case the
+/// [`MessageReaction::actor`] is known, and when the anonymous reaction counts change.
+#[derive(Clone)]
+pub struct MessageReaction {
+    raw: tl::enums::Update,
+    chats: Arc<ChatMap>,
+}
+
+/// The text representation of a reaction: either the emoji itself, or the document identifier
+/// of a custom emoji.
+pub(crate) fn reaction_string(reaction: &tl::enums::Reaction) -> Option<String> {
+    match reaction {
+        tl::enums::Reaction::Emoji(r) => Some(r.emoticon.clone()),
+        tl::enums::Reaction::CustomEmoji(r) => Some(r.document_id.to_string()),
+        tl::enums::Reaction::Empty => None,
+    }
+}
+
+fn reaction_counts(counts: &[tl::enums::ReactionCount]) -> Vec<(String, i32)> {
+    counts
+        .iter()
+        .filter_map(|count| {
+            let tl::enums::ReactionCount::Count(count) = count;
+            reaction_string(&count.reaction).map(|reaction| (reaction, count.count))
+        })
+        .collect()
+}
+
+fn reaction_strings(reactions: &[tl::enums::Reaction]) -> Vec<String> {
+    reactions.iter().filter_map(reaction_string).collect()
+}
+
+impl MessageReaction {
+    /// Creates a new `MessageReaction` if the update is about the reactions to a message.
+    pub(crate) fn new(update: tl::enums::Update, chats: &Arc<ChatMap>) -> Option<Self> {
+        match update {
+            tl::enums::Update::MessageReactions(_)
+            | tl::enums::Update::BotMessageReaction(_)
+            | tl::enums::Update::BotMessageReactions(_) => Some(Self {
+                raw: update,
+                chats: chats.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The peer of the chat where the reacted message is.
+    pub fn peer(&self) -> &tl::enums::Peer {
+        match &self.raw {
+            tl::enums::Update::MessageReactions(u) => &u.peer,
+            tl::enums::Update::BotMessageReaction(u) => &u.peer,
+            tl::enums::Update::BotMessageReactions(u) => &u.peer,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The chat where the reacted message is, if it's known.
+    pub fn chat(&self) -> Option<&Chat> {
+        self.chats.get(self.peer())
+    }
+
+    /// The identifier of the message whose reactions changed.
+    pub fn message_id(&self) -> i32 {
+        match &self.raw {
+            tl::enums::Update::MessageReactions(u) => u.msg_id,
+            tl::enums::Update::BotMessageReaction(u) => u.msg_id,
+            tl::enums::Update::BotMessageReactions(u) => u.msg_id,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The new amount of each reaction to the message, if the counts are what changed.
+    ///
+    /// Reactions are represented the same way as in [`Client::react_to_message`].
+    ///
+    /// [`Client::react_to_message`]: crate::Client::react_to_message
+    pub fn counts(&self) -> Option<Vec<(String, i32)>> {
+        match &self.raw {
+            tl::enums::Update::MessageReactions(u) => {
+                let tl::enums::MessageReactions::Reactions(reactions) = &u.reactions;
+                Some(reaction_counts(&reactions.results))
+            }
+            tl::enums::Update::BotMessageReactions(u) => Some(reaction_counts(&u.reactions)),
+            _ => None,
+        }
+    }
+
+    /// The peer who changed their reactions, if a single one did.
+    pub fn actor_peer(&self) -> Option<&tl::enums::Peer> {
+        match &self.raw {
+            tl::enums::Update::BotMessageReaction(u) => Some(&u.actor),
+            _ => None,
+        }
+    }
+
+    /// The user or chat who changed their reactions, if a single one did and it's known.
+    pub fn actor(&self) -> Option<&Chat> {
+        self.actor_peer().and_then(|peer| self.chats.get(peer))
+    }
+
+    /// The reactions the [`MessageReaction::actor`] had before the change, if a single one
+    /// changed them.
+    pub fn old_reactions(&self) -> Option<Vec<String>> {
+        match &self.raw {
+            tl::enums::Update::BotMessageReaction(u) => Some(reaction_strings(&u.old_reactions)),
+            _ => None,
+        }
+    }
+
+    /// The reactions the [`MessageReaction::actor`] has after the change, if a single one
+    /// changed them.
+    ///
+    /// The list is empty if the actor removed their reactions.
+    pub fn new_reactions(&self) -> Option<Vec<String>> {
+        match &self.raw {
+            tl::enums::Update::BotMessageReaction(u) => Some(reaction_strings(&u.new_reactions)),
+            _ => None,
+        }
+    }
+
+    /// The raw update as sent by Telegram.
+    pub fn raw(&self) -> &tl::enums::Update {
+        &self.raw
+    }
+}
+
+impl fmt::Debug for MessageReaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageReaction")
+            .field("chat", &self.chat())
+            .field("message_id", &self.message_id())
+            .field("actor", &self.actor())
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emoji(emoticon: &str) -> tl::enums::Reaction {
+        tl::types::ReactionEmoji {
+            emoticon: emoticon.to_string(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn bot_message_reaction_becomes_message_reaction() {
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 2 }.into()], Vec::new());
+        let update = tl::types::UpdateBotMessageReaction {
+            peer: tl::types::PeerChat { chat_id: 1 }.into(),
+            msg_id: 3,
+            date: 0,
+            actor: tl::types::PeerUser { user_id: 2 }.into(),
+            old_reactions: Vec::new(),
+            new_reactions: vec![
+                emoji("👍"),
+                tl::types::ReactionCustomEmoji { document_id: 4 }.into(),
+            ],
+            qts: 5,
+        };
+
+        let reaction = MessageReaction::new(update.into(), &chats).unwrap();
+        assert_eq!(reaction.message_id(), 3);
+        assert!(reaction.chat().is_none());
+        assert_eq!(reaction.actor().map(|a| a.id()), Some(2));
+        assert_eq!(reaction.old_reactions(), Some(Vec::new()));
+        assert_eq!(
+            reaction.new_reactions(),
+            Some(vec!["👍".to_string(), "4".to_string()])
+        );
+        assert_eq!(reaction.counts(), None);
+    }
+
+    #[test]
+    fn bot_message_reactions_becomes_message_reaction() {
+        let update = tl::types::UpdateBotMessageReactions {
+            peer: tl::types::PeerChat { chat_id: 1 }.into(),
+            msg_id: 3,
+            date: 0,
+            reactions: vec![tl::types::ReactionCount {
+                chosen_order: None,
+                reaction: emoji("🔥"),
+                count: 7,
+            }
+            .into()],
+            qts: 5,
+        };
+
+        let reaction = MessageReaction::new(update.into(), &ChatMap::empty()).unwrap();
+        assert_eq!(reaction.counts(), Some(vec![("🔥".to_string(), 7)]));
+        assert!(reaction.actor_peer().is_none());
+        assert_eq!(reaction.new_reactions(), None);
+    }
+
+    #[test]
+    fn other_updates_are_not_message_reactions() {
+        let update = tl::types::UpdateConfig {}.into();
+        assert!(MessageReaction::new(update, &ChatMap::empty()).is_none());
+    }
+}
@@ -7,48 +7,76 @@
 // except according to those terms.
 
 //! Custom types extending those provided by Telegram.
+pub mod admin_log;
+pub mod album_item;
 pub mod attributes;
 pub mod button;
 pub mod callback_query;
 pub mod chat;
+pub mod chat_action;
+pub mod chat_join_request;
 pub mod chat_map;
+pub mod chat_member_update;
 pub mod chats;
+pub mod chosen_inline_result;
 pub mod dialog;
 pub mod downloadable;
+pub mod entity;
+pub mod forward_origin;
+pub mod inline_keyboard;
 pub mod inline_query;
 pub mod input_message;
+pub mod input_poll;
+pub mod invite_link;
 pub mod iter_buffer;
 pub mod login_token;
 pub mod media;
 pub mod message;
 pub mod message_deletion;
+pub mod message_reaction;
 pub mod participant;
 pub mod password_token;
 pub mod permissions;
 pub mod photo_sizes;
+pub mod poll_update;
 pub mod reply_markup;
+pub mod scheduled_message;
 pub mod terms_of_service;
 pub mod update;
 
+pub use admin_log::{AdminLogAction, AdminLogEvent, AdminLogFilter};
+pub use album_item::AlbumItem;
 pub use attributes::Attribute;
 pub use callback_query::CallbackQuery;
 pub use chat::{Channel, Chat, Group, PackedChat, Platform, RestrictionReason, User};
+pub use chat_action::ChatAction;
+pub use chat_join_request::ChatJoinRequest;
 pub use chat_map::ChatMap;
 pub(crate) use chat_map::Peer;
+pub use chat_member_update::ChatMemberUpdate;
 pub use chats::{AdminRightsBuilder, BannedRightsBuilder};
+pub use chosen_inline_result::ChosenInlineResult;
 pub use dialog::Dialog;
 pub use downloadable::{ChatPhoto, Downloadable, UserProfilePhoto};
+pub use entity::EntityKind;
+pub use forward_origin::ForwardOrigin;
+pub use inline_keyboard::{InlineButton, InlineKeyboard};
 pub use inline_query::InlineQuery;
 pub use input_message::InputMessage;
+pub use input_poll::InputPoll;
+pub use invite_link::{InviteLink, InviteLinkOptions};
 pub use iter_buffer::IterBuffer;
 pub use login_token::LoginToken;
 pub(crate) use media::Uploaded;
 pub use media::{Media, Photo};
 pub use message::Message;
 pub use message_deletion::MessageDeletion;
+pub use message_reaction::MessageReaction;
 pub use participant::{Participant, Role};
 pub use password_token::PasswordToken;
 pub use permissions::{Permissions, Restrictions};
+pub use poll_update::PollUpdate;
 pub(crate) use reply_markup::ReplyMarkup;
+pub use scheduled_message::ScheduledMessage;
 pub use terms_of_service::TermsOfService;
 pub use update::Update;
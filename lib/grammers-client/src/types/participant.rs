@@ -12,8 +12,8 @@ use grammers_tl_types as tl;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Normal {
-    date: i32,
-    inviter_id: Option<i64>,
+    pub(crate) date: i32,
+    pub(crate) inviter_id: Option<i64>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -124,65 +124,113 @@ impl Banned {
     }
 }
 
+impl Role {
+    pub(crate) fn from_raw_channel(participant: tl::enums::ChannelParticipant) -> Self {
+        use tl::enums::ChannelParticipant as P;
+
+        match participant {
+            P::Participant(p) => Role::User(Normal {
+                date: p.date,
+                inviter_id: None,
+            }),
+            P::ParticipantSelf(p) => Role::User(Normal {
+                date: p.date,
+                inviter_id: Some(p.inviter_id),
+            }),
+            P::Creator(p) => Role::Creator(Creator {
+                permissions: Permissions::from_raw(p.admin_rights.into()),
+                rank: p.rank,
+            }),
+            P::Admin(p) => Role::Admin(Admin {
+                can_edit: p.can_edit,
+                inviter_id: p.inviter_id,
+                promoted_by: Some(p.promoted_by),
+                date: p.date,
+                permissions: Permissions::from_raw(p.admin_rights.into()),
+                rank: p.rank,
+            }),
+            P::Banned(p) => Role::Banned(Banned {
+                left: p.left,
+                kicked_by: p.kicked_by,
+                date: p.date,
+                restrictions: Restrictions::from_raw(p.banned_rights.into()),
+            }),
+            P::Left(_) => Role::Left(Left {}),
+        }
+    }
+
+    pub(crate) fn from_raw_chat(participant: tl::enums::ChatParticipant) -> Self {
+        use tl::enums::ChatParticipant as P;
+
+        match participant {
+            P::Participant(p) => Role::User(Normal {
+                date: p.date,
+                inviter_id: Some(p.inviter_id),
+            }),
+            P::Creator(_) => Role::Creator(Creator {
+                permissions: Permissions::new_full(),
+                rank: None,
+            }),
+            P::Admin(p) => Role::Admin(Admin {
+                can_edit: true,
+                inviter_id: Some(p.inviter_id),
+                promoted_by: None,
+                date: p.date,
+                permissions: Permissions::new_full(),
+                rank: None,
+            }),
+        }
+    }
+}
+
 impl Participant {
+    /// The user participating in the chat.
+    pub fn user(&self) -> &crate::types::User {
+        &self.user
+    }
+
+    /// The role of the user in the chat.
+    pub fn role(&self) -> &Role {
+        &self.role
+    }
+
+    /// When the user joined the chat, if it's known.
+    ///
+    /// For administrators this is the date they were promoted, and for banned users, the date of
+    /// the ban. Telegram does not provide this information for the creator of the chat.
+    pub fn joined_date(&self) -> Option<DateTime<Utc>> {
+        match &self.role {
+            Role::User(role) => Some(role.date()),
+            Role::Admin(role) => Some(role.date()),
+            Role::Banned(role) => Some(role.date()),
+            Role::Creator(_) | Role::Left(_) => None,
+        }
+    }
+
     pub(crate) fn from_raw_channel(
         chats: &mut ChatMap,
         participant: tl::enums::ChannelParticipant,
     ) -> Self {
         use tl::enums::ChannelParticipant as P;
 
-        match participant {
-            P::Participant(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::User(Normal {
-                    date: p.date,
-                    inviter_id: None,
-                }),
-            },
-            P::ParticipantSelf(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::User(Normal {
-                    date: p.date,
-                    inviter_id: Some(p.inviter_id),
-                }),
-            },
-            P::Creator(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::Creator(Creator {
-                    permissions: Permissions::from_raw(p.admin_rights.into()),
-                    rank: p.rank,
-                }),
-            },
-            P::Admin(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::Admin(Admin {
-                    can_edit: p.can_edit,
-                    inviter_id: p.inviter_id,
-                    promoted_by: Some(p.promoted_by),
-                    date: p.date,
-                    permissions: Permissions::from_raw(p.admin_rights.into()),
-                    rank: p.rank,
-                }),
+        let user = match &participant {
+            P::Participant(p) => chats.remove_user(p.user_id).unwrap(),
+            P::ParticipantSelf(p) => chats.remove_user(p.user_id).unwrap(),
+            P::Creator(p) => chats.remove_user(p.user_id).unwrap(),
+            P::Admin(p) => chats.remove_user(p.user_id).unwrap(),
+            P::Banned(p) => match chats.remove(&p.peer).unwrap() {
+                Chat::User(user) => user,
+                _ => todo!("figure out how to deal with non-user being banned"),
             },
-            P::Banned(p) => Self {
-                user: match chats.remove(&p.peer).unwrap() {
-                    Chat::User(user) => user,
-                    _ => todo!("figure out how to deal with non-user being banned"),
-                },
-                role: Role::Banned(Banned {
-                    left: p.left,
-                    kicked_by: p.kicked_by,
-                    date: p.date,
-                    restrictions: Restrictions::from_raw(p.banned_rights.into()),
-                }),
-            },
-            P::Left(p) => Self {
-                user: match chats.remove(&p.peer).unwrap() {
-                    Chat::User(user) => user,
-                    _ => todo!("figure out how to deal with non-user leaving"),
-                },
-                role: Role::Left(Left {}),
+            P::Left(p) => match chats.remove(&p.peer).unwrap() {
+                Chat::User(user) => user,
+                _ => todo!("figure out how to deal with non-user leaving"),
             },
+        };
+
+        Self {
+            user,
+            role: Role::from_raw_channel(participant),
         }
     }
 
@@ -192,32 +240,47 @@ impl Participant {
     ) -> Self {
         use tl::enums::ChatParticipant as P;
 
-        match participant {
-            P::Participant(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::User(Normal {
-                    date: p.date,
-                    inviter_id: Some(p.inviter_id),
-                }),
-            },
-            P::Creator(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::Creator(Creator {
-                    permissions: Permissions::new_full(),
-                    rank: None,
-                }),
-            },
-            P::Admin(p) => Self {
-                user: chats.remove_user(p.user_id).unwrap(),
-                role: Role::Admin(Admin {
-                    can_edit: true,
-                    inviter_id: Some(p.inviter_id),
-                    promoted_by: None,
-                    date: p.date,
-                    permissions: Permissions::new_full(),
-                    rank: None,
-                }),
-            },
+        let user_id = match &participant {
+            P::Participant(p) => p.user_id,
+            P::Creator(p) => p.user_id,
+            P::Admin(p) => p.user_id,
+        };
+
+        Self {
+            user: chats.remove_user(user_id).unwrap(),
+            role: Role::from_raw_chat(participant),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(role: Role) -> Participant {
+        Participant {
+            user: crate::types::User::from_raw(tl::types::UserEmpty { id: 1 }.into()),
+            role,
         }
     }
+
+    #[test]
+    fn joined_date_comes_from_role() {
+        let member = participant(Role::from_raw_chat(
+            tl::types::ChatParticipant {
+                user_id: 1,
+                inviter_id: 2,
+                date: 3,
+            }
+            .into(),
+        ));
+        assert_eq!(member.user().id(), 1);
+        assert!(matches!(member.role(), Role::User(_)));
+        assert_eq!(member.joined_date().map(|d| d.timestamp()), Some(3));
+
+        let creator = participant(Role::from_raw_chat(
+            tl::types::ChatParticipantCreator { user_id: 1 }.into(),
+        ));
+        assert_eq!(creator.joined_date(), None);
+    }
 }
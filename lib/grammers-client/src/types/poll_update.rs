@@ -0,0 +1,138 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::{Chat, ChatMap};
+use grammers_tl_types as tl;
+use std::fmt;
+use std::sync::Arc;
+
+/// Occurs whenever the results of a poll change, or when a user votes in a poll sent by the bot.
+#[derive(Clone)]
+pub struct PollUpdate {
+    raw: tl::enums::Update,
+    chats: Arc<ChatMap>,
+}
+
+impl PollUpdate {
+    /// Creates a new `PollUpdate` if the update is about a poll.
+    pub(crate) fn new(update: tl::enums::Update, chats: &Arc<ChatMap>) -> Option<Self> {
+        match update {
+            tl::enums::Update::MessagePoll(_) | tl::enums::Update::MessagePollVote(_) => {
+                Some(Self {
+                    raw: update,
+                    chats: chats.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The identifier of the poll.
+    pub fn poll_id(&self) -> i64 {
+        match &self.raw {
+            tl::enums::Update::MessagePoll(u) => u.poll_id,
+            tl::enums::Update::MessagePollVote(u) => u.poll_id,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The new results of the poll, if the results changed.
+    pub fn results(&self) -> Option<&tl::types::PollResults> {
+        match &self.raw {
+            tl::enums::Update::MessagePoll(u) => match &u.results {
+                tl::enums::PollResults::Results(results) => Some(results),
+            },
+            _ => None,
+        }
+    }
+
+    /// The peer who voted, if this update occurred because of a vote in a poll sent by the bot.
+    pub fn voter_peer(&self) -> Option<&tl::enums::Peer> {
+        match &self.raw {
+            tl::enums::Update::MessagePollVote(u) => Some(&u.peer),
+            _ => None,
+        }
+    }
+
+    /// The user or channel who voted, if this update occurred because of a vote and the voter is
+    /// known.
+    pub fn voter(&self) -> Option<&Chat> {
+        self.voter_peer().and_then(|peer| self.chats.get(peer))
+    }
+
+    /// The options chosen by the voter, if this update occurred because of a vote.
+    ///
+    /// The list is empty if the voter retracted their vote.
+    pub fn voter_options(&self) -> Option<&[Vec<u8>]> {
+        match &self.raw {
+            tl::enums::Update::MessagePollVote(u) => Some(&u.options),
+            _ => None,
+        }
+    }
+
+    /// The raw update as sent by Telegram.
+    pub fn raw(&self) -> &tl::enums::Update {
+        &self.raw
+    }
+}
+
+impl fmt::Debug for PollUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollUpdate")
+            .field("voter", &self.voter())
+            .field("raw", &self.raw)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_poll_becomes_poll_update() {
+        let update = tl::types::UpdateMessagePoll {
+            poll_id: 1,
+            poll: None,
+            results: tl::types::PollResults {
+                min: false,
+                results: None,
+                total_voters: Some(2),
+                recent_voters: None,
+                solution: None,
+                solution_entities: None,
+            }
+            .into(),
+        };
+
+        let poll = PollUpdate::new(update.into(), &ChatMap::empty()).unwrap();
+        assert_eq!(poll.poll_id(), 1);
+        assert_eq!(poll.results().unwrap().total_voters, Some(2));
+        assert!(poll.voter_peer().is_none());
+    }
+
+    #[test]
+    fn message_poll_vote_becomes_poll_update() {
+        let update = tl::types::UpdateMessagePollVote {
+            poll_id: 1,
+            peer: tl::types::PeerUser { user_id: 2 }.into(),
+            options: vec![vec![0]],
+            qts: 3,
+        };
+
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 2 }.into()], Vec::new());
+        let poll = PollUpdate::new(update.into(), &chats).unwrap();
+        assert_eq!(poll.poll_id(), 1);
+        assert!(poll.results().is_none());
+        assert_eq!(poll.voter().map(|v| v.id()), Some(2));
+        assert_eq!(
+            poll.voter_peer(),
+            Some(&tl::types::PeerUser { user_id: 2 }.into())
+        );
+        assert_eq!(poll.voter_options(), Some(&[vec![0]][..]));
+    }
+}
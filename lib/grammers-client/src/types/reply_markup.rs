@@ -58,7 +58,7 @@ impl ReplyMarkup for Keyboard {
 
 impl ReplyMarkup for Hide {
     fn to_reply_markup(&self) -> Markup {
-        Markup(self.0.clone().into())
+        Markup(self.0.into())
     }
 }
 
@@ -0,0 +1,39 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use super::Message;
+use chrono::{DateTime, Utc};
+
+/// A message which is scheduled to be sent later, and has not been sent yet.
+///
+/// Scheduled messages look the same as any other, but their identifier only refers to the
+/// scheduled message, not the one sent later.
+#[derive(Clone, Debug)]
+pub struct ScheduledMessage {
+    message: Message,
+}
+
+impl ScheduledMessage {
+    pub(crate) fn new(message: Message) -> Self {
+        Self { message }
+    }
+
+    /// The message as it will be sent.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// When the message will be sent.
+    pub fn send_date(&self) -> DateTime<Utc> {
+        self.message.date()
+    }
+
+    /// Take the message as it will be sent.
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+}
@@ -9,7 +9,10 @@ use std::sync::Arc;
 
 use grammers_tl_types as tl;
 
-use super::{CallbackQuery, ChatMap, InlineQuery, Message};
+use super::{
+    CallbackQuery, Chat, ChatAction, ChatJoinRequest, ChatMap, ChatMemberUpdate,
+    ChosenInlineResult, InlineQuery, Message, MessageReaction, PollUpdate, User,
+};
 use crate::{types::MessageDeletion, Client};
 
 #[non_exhaustive]
@@ -27,6 +30,36 @@ pub enum Update {
     /// Occurs whenever you sign in as a bot and a user sends an inline query
     /// such as `@bot query`.
     InlineQuery(InlineQuery),
+    /// Occurs whenever a user chooses one of the results of your bot's inline query.
+    ///
+    /// Only bots with inline feedback enabled will receive this update.
+    ChosenInlineResult(ChosenInlineResult),
+    /// Occurs whenever a participant joins, leaves, or has their role changed in a group or
+    /// channel.
+    ChatMemberUpdate(ChatMemberUpdate),
+    /// Occurs whenever a user asks to join a group or channel where new members need to be
+    /// approved.
+    ///
+    /// Only bots which can invite users to the chat will receive this update.
+    ChatJoinRequest(ChatJoinRequest),
+    /// Occurs whenever the results of a poll change, or when a user votes in a poll sent by the
+    /// bot.
+    PollUpdate(PollUpdate),
+    /// Occurs whenever the reactions to a message change.
+    MessageReaction(MessageReaction),
+    /// Occurs whenever someone is performing an action in a chat, such as typing or uploading a
+    /// photo.
+    ChatAction(ChatAction),
+    /// Occurs whenever a user goes online or offline, or their last seen status otherwise
+    /// changes.
+    UserStatusUpdate {
+        /// The identifier of the user whose status changed.
+        user_id: i64,
+        /// The user whose status changed, if it's known.
+        user: Option<User>,
+        /// The new status of the user.
+        status: tl::enums::UserStatus,
+    },
     /// Raw events are not actual events.
     /// Instead, they are the raw Update object that Telegram sends. You
     /// normally shouldn’t need these.
@@ -84,8 +117,104 @@ impl Update {
                 Some(Self::InlineQuery(InlineQuery::new(client, query, chats)))
             }
 
+            // ChosenInlineResult
+            tl::enums::Update::BotInlineSend(update) => Some(Self::ChosenInlineResult(
+                ChosenInlineResult::new(update, chats),
+            )),
+
+            // ChatMemberUpdate
+            update @ (tl::enums::Update::ChatParticipantAdd(_)
+            | tl::enums::Update::ChatParticipantDelete(_)
+            | tl::enums::Update::ChatParticipant(_)
+            | tl::enums::Update::ChannelParticipant(_)) => {
+                ChatMemberUpdate::new(update, chats).map(Self::ChatMemberUpdate)
+            }
+
+            // ChatJoinRequest
+            tl::enums::Update::BotChatInviteRequester(update) => {
+                Some(Self::ChatJoinRequest(ChatJoinRequest::new(update, chats)))
+            }
+
+            // PollUpdate
+            update
+            @ (tl::enums::Update::MessagePoll(_) | tl::enums::Update::MessagePollVote(_)) => {
+                PollUpdate::new(update, chats).map(Self::PollUpdate)
+            }
+
+            // MessageReaction
+            update @ (tl::enums::Update::MessageReactions(_)
+            | tl::enums::Update::BotMessageReaction(_)
+            | tl::enums::Update::BotMessageReactions(_)) => {
+                MessageReaction::new(update, chats).map(Self::MessageReaction)
+            }
+
+            // ChatAction
+            update @ (tl::enums::Update::UserTyping(_)
+            | tl::enums::Update::ChatUserTyping(_)
+            | tl::enums::Update::ChannelUserTyping(_)) => {
+                ChatAction::new(update, chats).map(Self::ChatAction)
+            }
+
+            // UserStatusUpdate
+            tl::enums::Update::UserStatus(update) => Some(Self::user_status(update, chats)),
+
             // Raw
             update => Some(Self::Raw(update)),
         }
     }
+
+    /// Creates a `UserStatusUpdate`, resolving the user through the chats if it's known.
+    fn user_status(update: tl::types::UpdateUserStatus, chats: &ChatMap) -> Self {
+        let tl::types::UpdateUserStatus { user_id, status } = update;
+        let user = match chats.get(&tl::types::PeerUser { user_id }.into()) {
+            Some(Chat::User(user)) => Some(user.clone()),
+            _ => None,
+        };
+        Self::UserStatusUpdate {
+            user_id,
+            user,
+            status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update_user_status(user_id: i64) -> tl::types::UpdateUserStatus {
+        tl::types::UpdateUserStatus {
+            user_id,
+            status: tl::types::UserStatusOnline { expires: 10 }.into(),
+        }
+    }
+
+    #[test]
+    fn user_status_resolves_known_user() {
+        let chats = ChatMap::new(vec![tl::types::UserEmpty { id: 1 }.into()], Vec::new());
+
+        match Update::user_status(update_user_status(1), &chats) {
+            Update::UserStatusUpdate {
+                user_id,
+                user,
+                status,
+            } => {
+                assert_eq!(user_id, 1);
+                assert_eq!(user.map(|user| user.id()), Some(1));
+                assert_eq!(status, tl::types::UserStatusOnline { expires: 10 }.into());
+            }
+            update => panic!("unexpected update: {:?}", update),
+        }
+    }
+
+    #[test]
+    fn user_status_keeps_unknown_user_id() {
+        match Update::user_status(update_user_status(2), &ChatMap::empty()) {
+            Update::UserStatusUpdate { user_id, user, .. } => {
+                assert_eq!(user_id, 2);
+                assert!(user.is_none());
+            }
+            update => panic!("unexpected update: {:?}", update),
+        }
+    }
 }
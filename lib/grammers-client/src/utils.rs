@@ -8,6 +8,8 @@
 
 use crate::types;
 use chrono::{DateTime, Utc};
+use grammers_mtproto::mtp::RpcError;
+use grammers_mtsender::InvocationError;
 use grammers_session::{PackedChat, PackedType};
 use grammers_tl_types as tl;
 use std::sync::atomic::{AtomicI64, Ordering};
@@ -44,6 +46,18 @@ pub(crate) fn date(date: i32) -> Date {
     DateTime::from_timestamp(date as i64, 0).expect("date out of range")
 }
 
+/// The timestamp of a date to schedule a message at, failing if Telegram could not represent it.
+pub(crate) fn schedule_date(date: Date) -> Result<i32, InvocationError> {
+    i32::try_from(date.timestamp()).map_err(|_| {
+        InvocationError::Rpc(RpcError {
+            code: 400,
+            name: "SCHEDULE_DATE_INVALID".to_string(),
+            value: None,
+            caused_by: None,
+        })
+    })
+}
+
 pub(crate) fn extract_password_parameters(
     current_algo: &tl::enums::PasswordKdfAlgo,
 ) -> (&Vec<u8>, &Vec<u8>, &Vec<u8>, &i32) {
@@ -107,7 +107,10 @@ impl Deserializable for RpcResult {
     fn deserialize(buf: &mut Cursor) -> Result<Self, tl::deserialize::Error> {
         let constructor_id = u32::deserialize(buf)?;
         if constructor_id != Self::CONSTRUCTOR_ID {
-            return Err(tl::deserialize::Error::UnexpectedConstructor { id: constructor_id });
+            return Err(tl::deserialize::Error::UnexpectedConstructor {
+                id: constructor_id,
+                expected: "RpcResult",
+            });
         }
 
         let req_msg_id = i64::deserialize(buf)?;
@@ -156,7 +159,10 @@ impl Deserializable for MessageContainer {
     fn deserialize(buf: &mut Cursor) -> Result<Self, tl::deserialize::Error> {
         let constructor_id = u32::deserialize(buf)?;
         if constructor_id != Self::CONSTRUCTOR_ID {
-            return Err(tl::deserialize::Error::UnexpectedConstructor { id: constructor_id });
+            return Err(tl::deserialize::Error::UnexpectedConstructor {
+                id: constructor_id,
+                expected: "MessageContainer",
+            });
         }
 
         let len = i32::deserialize(buf)?;
@@ -235,7 +241,10 @@ impl Deserializable for GzipPacked {
     fn deserialize(buf: &mut Cursor) -> Result<Self, tl::deserialize::Error> {
         let constructor_id = u32::deserialize(buf)?;
         if constructor_id != Self::CONSTRUCTOR_ID {
-            return Err(tl::deserialize::Error::UnexpectedConstructor { id: constructor_id });
+            return Err(tl::deserialize::Error::UnexpectedConstructor {
+                id: constructor_id,
+                expected: "GzipPacked",
+            });
         }
 
         let packed_data = Vec::<u8>::deserialize(buf)?;
@@ -77,8 +77,8 @@ pub enum DeserializeError {
 
     /// While deserializing the response types one of them had a constructor
     /// that did not match our expectations. The invalid ID is contained
-    /// within this variant.
-    UnexpectedConstructor { id: u32 },
+    /// within this variant, along with the name of the type expected instead.
+    UnexpectedConstructor { id: u32, expected: &'static str },
 
     /// Attempting to decrypt the message failed in some way.
     DecryptionError(crypto::Error),
@@ -111,7 +111,11 @@ impl fmt::Display for DeserializeError {
                 "server responded with a payload that's too small to fit a valid message"
             ),
             Self::DecompressionFailed => write!(f, "failed to decompress server's data"),
-            Self::UnexpectedConstructor { id } => write!(f, "unexpected constructor: {:08x}", id),
+            Self::UnexpectedConstructor { id, expected } => write!(
+                f,
+                "unexpected constructor {:08x} while deserializing {}",
+                id, expected
+            ),
             Self::DecryptionError(ref error) => write!(f, "failed to decrypt message: {}", error),
         }
     }
@@ -123,7 +127,9 @@ impl From<tl::deserialize::Error> for DeserializeError {
 
         match error {
             Err::UnexpectedEof => DeserializeError::MessageBufferTooSmall,
-            Err::UnexpectedConstructor { id } => DeserializeError::UnexpectedConstructor { id },
+            Err::UnexpectedConstructor { id, expected } => {
+                DeserializeError::UnexpectedConstructor { id, expected }
+            }
         }
     }
 }
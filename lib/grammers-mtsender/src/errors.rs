@@ -9,6 +9,7 @@ use grammers_mtproto::{authentication, mtp, transport};
 use grammers_tl_types as tl;
 use std::fmt;
 use std::io;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum ReadError {
@@ -68,6 +69,9 @@ pub enum InvocationError {
 
     /// The error occured while reading the response.
     Read(ReadError),
+
+    /// The response did not arrive in time, and the request was given up on.
+    Timeout,
 }
 
 impl std::error::Error for InvocationError {}
@@ -78,6 +82,7 @@ impl fmt::Display for InvocationError {
             Self::Rpc(err) => write!(f, "request error: {}", err),
             Self::Dropped => write!(f, "request error: dropped (cancelled)"),
             Self::Read(err) => write!(f, "request error: {}", err),
+            Self::Timeout => write!(f, "request error: timed out"),
         }
     }
 }
@@ -127,6 +132,35 @@ impl InvocationError {
             _ => false,
         }
     }
+
+    /// How long Telegram asked to wait before making the request again.
+    ///
+    /// This is only known for the `FLOOD_WAIT`, `FLOOD_PREMIUM_WAIT` and `SLOWMODE_WAIT` RPC
+    /// errors. For any other error, returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async fn f(result: Result<(), grammers_mtsender::InvocationError>) {
+    /// if let Err(err) = result {
+    ///     if let Some(delay) = err.flood_wait() {
+    ///         tokio::time::sleep(delay).await;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn flood_wait(&self) -> Option<Duration> {
+        match self {
+            Self::Rpc(rpc)
+                if rpc.is("FLOOD_WAIT")
+                    || rpc.is("FLOOD_PREMIUM_WAIT")
+                    || rpc.is("SLOWMODE_WAIT") =>
+            {
+                rpc.value.map(|secs| Duration::from_secs(secs as u64))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// This error occurs when the process to generate an authorization key fails.
@@ -169,3 +203,49 @@ impl From<io::Error> for AuthorizationError {
         Self::from(InvocationError::from(ReadError::from(error)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_error(message: &str) -> InvocationError {
+        InvocationError::Rpc(
+            tl::types::RpcError {
+                error_code: 420,
+                error_message: message.to_string(),
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn flood_wait_is_parsed() {
+        assert_eq!(
+            rpc_error("FLOOD_WAIT_31").flood_wait(),
+            Some(Duration::from_secs(31))
+        );
+    }
+
+    #[test]
+    fn flood_premium_wait_is_parsed() {
+        assert_eq!(
+            rpc_error("FLOOD_PREMIUM_WAIT_5").flood_wait(),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn slowmode_wait_is_parsed() {
+        assert_eq!(
+            rpc_error("SLOWMODE_WAIT_60").flood_wait(),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn other_errors_have_no_flood_wait() {
+        assert_eq!(rpc_error("PEER_FLOOD").flood_wait(), None);
+        assert_eq!(rpc_error("FLOOD_TEST_PHONE_WAIT_3").flood_wait(), None);
+        assert_eq!(InvocationError::Dropped.flood_wait(), None);
+    }
+}
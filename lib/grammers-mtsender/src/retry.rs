@@ -1,5 +1,11 @@
+use std::future::Future;
+
+use crate::InvocationError;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::ops::ControlFlow;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// a simple **Reconnection** Handler.
 ///
@@ -16,6 +22,56 @@ pub trait RetryPolicy: Send + Sync {
     ///
     /// it accepts a `attempts` which is the amount of reconnection tries that has been made already
     fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration>;
+
+    /// same as `should_retry`, but for a request that failed with `err`, so that errors which will never succeed
+    /// can be given up on immediately.
+    ///
+    /// this is what the `retrying!` macro calls for `InvocationError`s. by default, the error is ignored and
+    /// `should_retry` decides.
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        let _ = err;
+        self.should_retry(attempts)
+    }
+}
+
+impl<T: RetryPolicy + ?Sized> RetryPolicy for &T {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        (**self).should_retry(attempts)
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        (**self).should_retry_err(attempts, err)
+    }
+}
+
+/// an *asynchronous* variant of the **ReconnectionPolicy**.
+///
+/// this is useful for policies that need to `await` before deciding, for example, to consult an external rate
+/// limiter. every `RetryPolicy` is also an `AsyncRetryPolicy`, so use the `retrying_async!` macro with either.
+pub trait AsyncRetryPolicy: Send + Sync {
+    /// same as `RetryPolicy::should_retry`, but the decision is made asynchronously.
+    fn should_retry_async(
+        &self,
+        attempts: usize,
+    ) -> impl Future<Output = ControlFlow<(), Duration>> + Send;
+}
+
+impl<T: RetryPolicy + ?Sized> AsyncRetryPolicy for T {
+    fn should_retry_async(
+        &self,
+        attempts: usize,
+    ) -> impl Future<Output = ControlFlow<(), Duration>> + Send {
+        let flow = self.should_retry(attempts);
+        async move { flow }
+    }
 }
 
 /// the default implementation of the **ReconnectionPolicy**.
@@ -43,14 +99,551 @@ impl RetryPolicy for Fixed {
     }
 }
 
+/// *Exponential* backoff implementation for the **ReconnectionPolicy** trait.
+///
+/// the first retry waits for `initial_delay`, and every following attempt multiplies the previous delay
+/// by `multiplier`, never waiting longer than `max_delay`. note that this policy never gives up on its own,
+/// unless `max_attempts` is set.
+pub struct ExponentialBackoff {
+    pub initial_delay: Duration,
+    pub multiplier: f64,
+    pub max_delay: Duration,
+    pub max_attempts: Option<usize>,
+}
+
+impl ExponentialBackoff {
+    /// # Panics
+    ///
+    /// panics if `multiplier` is not a finite number, or if it is less than `1.0`.
+    pub fn new(initial_delay: Duration, multiplier: f64, max_delay: Duration) -> Self {
+        assert!(
+            multiplier.is_finite() && multiplier >= 1.0,
+            "the multiplier must be finite and at least 1.0, but it was {}",
+            multiplier
+        );
+        Self {
+            initial_delay,
+            multiplier,
+            max_delay,
+            max_attempts: None,
+        }
+    }
+
+    /// give up after the given amount of attempts, like **Fixed** does.
+    pub const fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// the delay to wait for before performing the given attempt (starting at `1`).
+    pub fn delay_for(&self, attempts: usize) -> Duration {
+        if self.initial_delay.is_zero() {
+            return Duration::ZERO;
+        }
+        let exponent = i32::try_from(attempts.saturating_sub(1)).unwrap_or(i32::MAX);
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        // Overflowing (or otherwise unrepresentable) delays are as long as they can be.
+        Duration::try_from_secs_f64(delay).map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        match self.max_attempts {
+            Some(max_attempts) if attempts > max_attempts => ControlFlow::Break(()),
+            _ => ControlFlow::Continue(self.delay_for(attempts)),
+        }
+    }
+}
+
+/// *Jittered* wrapper around another **ReconnectionPolicy**.
+///
+/// every delay returned by the `inner` policy is scaled by a random factor between `1 - jitter` and `1 + jitter`, so
+/// that many clients reconnecting at the same time (for example, after a server restart) don't all retry at once.
+/// the `jitter` is `0.5` unless changed with `with_jitter`.
+pub struct JitteredBackoff<P: RetryPolicy> {
+    pub inner: P,
+    jitter: f64,
+    rng: Mutex<StdRng>,
+}
+
+impl<P: RetryPolicy> JitteredBackoff<P> {
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            jitter: 0.5,
+            rng: Mutex::new(StdRng::from_entropy()),
+        }
+    }
+
+    /// same as `new`, but the jitter is deterministic for a given `seed`, which is mostly useful in tests.
+    pub fn with_seed(inner: P, seed: u64) -> Self {
+        Self {
+            inner,
+            jitter: 0.5,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// change how far, as a fraction of the delay, the jittered delay may be from the one of the `inner` policy.
+    ///
+    /// # Panics
+    ///
+    /// panics if `jitter` is not between `0.0` and `1.0`.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&jitter),
+            "the jitter must be between 0.0 and 1.0, but it was {}",
+            jitter
+        );
+        self.jitter = jitter;
+        self
+    }
+
+    fn jitter(&self, flow: ControlFlow<(), Duration>) -> ControlFlow<(), Duration> {
+        match flow {
+            ControlFlow::Continue(delay) => {
+                let offset = self
+                    .rng
+                    .lock()
+                    .unwrap()
+                    .gen_range(-self.jitter..=self.jitter);
+                ControlFlow::Continue(delay.mul_f64(1.0 + offset))
+            }
+            ControlFlow::Break(()) => ControlFlow::Break(()),
+        }
+    }
+}
+
+impl<P: RetryPolicy> RetryPolicy for JitteredBackoff<P> {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        self.jitter(self.inner.should_retry(attempts))
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        self.jitter(self.inner.should_retry_err(attempts, err))
+    }
+}
+
+/// *Time-bounded* wrapper around another **ReconnectionPolicy**.
+///
+/// the `inner` policy decides how long to wait between attempts, but once waiting for the next attempt would go
+/// past `budget` since the first attempt this policy gives up, no matter how many attempts the `inner` policy would
+/// still allow.
+///
+/// the clock starts again every time a new sequence of attempts begins (that is, when `attempts` is `1`).
+pub struct MaxDuration {
+    pub budget: Duration,
+    pub inner: Box<dyn RetryPolicy>,
+    start: Mutex<Option<Instant>>,
+}
+
+impl MaxDuration {
+    pub fn new(budget: Duration, inner: Box<dyn RetryPolicy>) -> Self {
+        Self {
+            budget,
+            inner,
+            start: Mutex::new(None),
+        }
+    }
+
+    fn decide(
+        &self,
+        attempts: usize,
+        inner: impl FnOnce() -> ControlFlow<(), Duration>,
+    ) -> ControlFlow<(), Duration> {
+        let now = Instant::now();
+        let start = {
+            let mut start = self.start.lock().unwrap();
+            if attempts <= 1 || start.is_none() {
+                *start = Some(now);
+            }
+            start.unwrap()
+        };
+
+        let elapsed = now - start;
+        if elapsed >= self.budget {
+            return ControlFlow::Break(());
+        }
+        match inner() {
+            ControlFlow::Continue(delay) if elapsed + delay > self.budget => ControlFlow::Break(()),
+            flow => flow,
+        }
+    }
+}
+
+impl RetryPolicy for MaxDuration {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        self.decide(attempts, || self.inner.should_retry(attempts))
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        self.decide(attempts, || self.inner.should_retry_err(attempts, err))
+    }
+}
+
+/// *Sequencing* of two **ReconnectionPolicy**.
+///
+/// the first `a_max` attempts are handled by `a`, and the rest by `b`, which sees the attempts as if they started
+/// from `1` again. this can be used to retry quickly a few times before falling back to slower retries.
+pub struct Chain<A: RetryPolicy, B: RetryPolicy> {
+    pub a: A,
+    pub b: B,
+    pub a_max: usize,
+}
+
+impl<A: RetryPolicy, B: RetryPolicy> Chain<A, B> {
+    pub const fn new(a: A, a_max: usize, b: B) -> Self {
+        Self { a, b, a_max }
+    }
+}
+
+impl<A: RetryPolicy, B: RetryPolicy> RetryPolicy for Chain<A, B> {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        if attempts <= self.a_max {
+            self.a.should_retry(attempts)
+        } else {
+            self.b.should_retry(attempts - self.a_max)
+        }
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        if attempts <= self.a_max {
+            self.a.should_retry_err(attempts, err)
+        } else {
+            self.b.should_retry_err(attempts - self.a_max, err)
+        }
+    }
+}
+
+/// *Error-aware* wrapper around another **ReconnectionPolicy**.
+///
+/// only errors which are an `InvocationError::Rpc` with one of the given `codes` are retried (as the `inner` policy
+/// decides), and every other error gives up immediately. this prevents retrying errors that will never succeed,
+/// such as a `400` `PHONE_NUMBER_BANNED`.
+///
+/// when the error is not known (such as when reconnecting), there is no code to match, so this gives up too.
+pub struct ErrorCodePolicy {
+    pub codes: &'static [i32],
+    pub inner: Box<dyn RetryPolicy>,
+}
+
+impl ErrorCodePolicy {
+    pub fn new(codes: &'static [i32], inner: Box<dyn RetryPolicy>) -> Self {
+        Self { codes, inner }
+    }
+}
+
+impl RetryPolicy for ErrorCodePolicy {
+    fn should_retry(&self, _: usize) -> ControlFlow<(), Duration> {
+        ControlFlow::Break(())
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        match err {
+            InvocationError::Rpc(rpc) if self.codes.contains(&rpc.code) => {
+                self.inner.should_retry_err(attempts, err)
+            }
+            _ => ControlFlow::Break(()),
+        }
+    }
+}
+
+/// *Transient-error* wrapper around another **ReconnectionPolicy**.
+///
+/// errors which did not come from the server (such as network errors or timeouts), internal server errors (`500`
+/// and above) and server-side timeouts (`-503`) are retried as the `inner` policy decides. flood waits are retried
+/// too, but never before the time Telegram asked to wait for. every other `InvocationError::Rpc` gives up
+/// immediately, because sending the same request again would fail in the same way.
+pub struct TransientErrorPolicy {
+    pub inner: Box<dyn RetryPolicy>,
+}
+
+impl TransientErrorPolicy {
+    pub fn new(inner: Box<dyn RetryPolicy>) -> Self {
+        Self { inner }
+    }
+}
+
+impl RetryPolicy for TransientErrorPolicy {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        self.inner.should_retry(attempts)
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        if let Some(wait) = err.flood_wait() {
+            return match self.inner.should_retry_err(attempts, err) {
+                ControlFlow::Continue(delay) => ControlFlow::Continue(delay.max(wait)),
+                flow => flow,
+            };
+        }
+        match err {
+            InvocationError::Rpc(rpc) if rpc.code < 500 && rpc.code != -503 => {
+                ControlFlow::Break(())
+            }
+            _ => self.inner.should_retry_err(attempts, err),
+        }
+    }
+}
+
+/// *Circuit-breaking* wrapper around another **ReconnectionPolicy**.
+///
+/// after `failure_threshold` consecutive failures the circuit *opens*, and every attempt is given up immediately
+/// until `open_duration` has passed. then a single attempt is allowed through (the circuit is *half-open*): if it
+/// fails again, the circuit opens once more, otherwise it *closes* and the `inner` policy is used as usual.
+///
+/// because the policy is only consulted on failures, a new sequence of attempts (when `attempts` is `1`) after an
+/// attempt was allowed through is what indicates that the previous one succeeded.
+pub struct CircuitBreaker {
+    pub failure_threshold: usize,
+    pub open_duration: Duration,
+    pub inner: Box<dyn RetryPolicy>,
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Default)]
+struct CircuitState {
+    failures: usize,
+    open_until: Option<Instant>,
+    half_open: bool,
+    last_continued: bool,
+}
+
+impl CircuitBreaker {
+    pub fn new(
+        failure_threshold: usize,
+        open_duration: Duration,
+        inner: Box<dyn RetryPolicy>,
+    ) -> Self {
+        Self {
+            failure_threshold,
+            open_duration,
+            inner,
+            state: Mutex::new(CircuitState::default()),
+        }
+    }
+
+    /// whether the circuit is currently open, and attempts are being given up immediately.
+    pub fn is_open(&self) -> bool {
+        match self.state.lock().unwrap().open_until {
+            Some(until) => Instant::now() < until,
+            None => false,
+        }
+    }
+
+    fn decide(
+        &self,
+        attempts: usize,
+        inner: impl FnOnce() -> ControlFlow<(), Duration>,
+    ) -> ControlFlow<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(until) = state.open_until {
+            if now < until {
+                return ControlFlow::Break(());
+            }
+            state.open_until = None;
+            state.failures = 0;
+            state.half_open = true;
+        } else if state.half_open {
+            state.half_open = false;
+            if attempts > 1 {
+                // The attempt allowed through failed, so open the circuit again.
+                state.open_until = Some(now + self.open_duration);
+                return ControlFlow::Break(());
+            }
+        } else if attempts <= 1 && state.last_continued {
+            state.failures = 0;
+        }
+
+        if !state.half_open {
+            state.failures += 1;
+            if state.failures >= self.failure_threshold {
+                state.failures = 0;
+                state.open_until = Some(now + self.open_duration);
+                return ControlFlow::Break(());
+            }
+        }
+
+        let flow = inner();
+        state.last_continued = flow.is_continue();
+        flow
+    }
+}
+
+impl RetryPolicy for CircuitBreaker {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        self.decide(attempts, || self.inner.should_retry(attempts))
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        self.decide(attempts, || self.inner.should_retry_err(attempts, err))
+    }
+}
+
+/// *Observable* wrapper around another **ReconnectionPolicy**.
+///
+/// every time the `inner` policy decides to retry, `on_retry` is called with the attempt number and the delay before
+/// the decision is returned. this can be used to log or count retries without changing the policy itself.
+pub struct OnRetry<P: RetryPolicy, F: Fn(usize, Duration) + Send + Sync> {
+    pub inner: P,
+    pub on_retry: F,
+}
+
+impl<P: RetryPolicy, F: Fn(usize, Duration) + Send + Sync> OnRetry<P, F> {
+    pub const fn new(inner: P, on_retry: F) -> Self {
+        Self { inner, on_retry }
+    }
+
+    fn observe(
+        &self,
+        attempts: usize,
+        flow: ControlFlow<(), Duration>,
+    ) -> ControlFlow<(), Duration> {
+        if let ControlFlow::Continue(delay) = flow {
+            (self.on_retry)(attempts, delay);
+        }
+        flow
+    }
+}
+
+impl<P: RetryPolicy, F: Fn(usize, Duration) + Send + Sync> RetryPolicy for OnRetry<P, F> {
+    fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+        self.observe(attempts, self.inner.should_retry(attempts))
+    }
+
+    fn should_retry_err(
+        &self,
+        attempts: usize,
+        err: &InvocationError,
+    ) -> ControlFlow<(), Duration> {
+        self.observe(attempts, self.inner.should_retry_err(attempts, err))
+    }
+}
+
 impl RetryPolicy for NoRetry {
     fn should_retry(&self, _: usize) -> ControlFlow<(), Duration> {
         ControlFlow::Break(())
     }
 }
 
+/// implementation detail of the `retrying!` macro, which picks `should_retry_err` when the error is an
+/// `InvocationError`, and `should_retry` for any other error type.
+#[doc(hidden)]
+pub mod __private {
+    use super::RetryPolicy;
+    use crate::InvocationError;
+    use std::ops::ControlFlow;
+    use std::time::Duration;
+
+    pub struct Decide<'a, P: ?Sized, E>(pub &'a P, pub usize, pub &'a E);
+
+    pub trait DecideWithError {
+        fn decide(&self) -> ControlFlow<(), Duration>;
+    }
+
+    impl<P: RetryPolicy + ?Sized> DecideWithError for Decide<'_, P, InvocationError> {
+        fn decide(&self) -> ControlFlow<(), Duration> {
+            self.0.should_retry_err(self.1, self.2)
+        }
+    }
+
+    pub trait DecideWithAttempts {
+        fn decide(&self) -> ControlFlow<(), Duration>;
+    }
+
+    impl<P: RetryPolicy + ?Sized, E> DecideWithAttempts for &Decide<'_, P, E> {
+        fn decide(&self) -> ControlFlow<(), Duration> {
+            self.0.should_retry(self.1)
+        }
+    }
+}
+
+/// run `$body` again for as long as it fails and the `$policy` decides to retry.
+///
+/// when the error is an `InvocationError`, the policy is asked through `should_retry_err`, and otherwise through
+/// `should_retry`, so any error type can be retried.
 #[macro_export]
 macro_rules! retrying {
+    ($policy:expr, $body:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::retry::__private::{DecideWithAttempts as _, DecideWithError as _};
+        let mut attempts = 0;
+        loop {
+            let res = $body;
+            attempts += 1;
+            match res {
+                Ok(value) => {
+                    break Ok(value);
+                }
+                Err(ref err) => {
+                    match (&$crate::retry::__private::Decide(&$policy, attempts, err)).decide() {
+                        std::ops::ControlFlow::Continue(timeout) => {
+                            tokio::time::sleep(timeout).await;
+                            continue;
+                        }
+                        std::ops::ControlFlow::Break(_) => break res,
+                    }
+                }
+            }
+        }
+    }};
+}
+
+/// same as `retrying!`, but as a function which calls `op` again every time the `policy` decides to retry.
+///
+/// unlike the macro, this can be passed around and used with closures that capture by move. because the error
+/// can be of any type, the policy is only asked through `should_retry`. use `retry_invocation` to let the policy
+/// inspect `InvocationError`s.
+pub async fn retry_async<T, E, F, Fut>(policy: &dyn RetryPolicy, mut op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retrying!(policy, op().await)
+}
+
+/// same as `retry_async`, but the `policy` is asked through `should_retry_err`, so it can give up early on
+/// errors that will never succeed.
+pub async fn retry_invocation<T, F, Fut>(
+    policy: &dyn RetryPolicy,
+    mut op: F,
+) -> Result<T, InvocationError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, InvocationError>>,
+{
+    retrying!(policy, op().await)
+}
+
+/// same as `retrying!`, but `await`s the decision of an `AsyncRetryPolicy`.
+#[macro_export]
+macro_rules! retrying_async {
     ($policy:expr, $body:expr) => {{
         let mut attempts = 0;
         loop {
@@ -60,13 +653,17 @@ macro_rules! retrying {
                 Ok(value) => {
                     break Ok(value);
                 }
-                Err(_) => match $policy.should_retry(attempts) {
-                    std::ops::ControlFlow::Continue(timeout) => {
-                        tokio::time::sleep(timeout).await;
-                        continue;
+                Err(_) => {
+                    match $crate::retry::AsyncRetryPolicy::should_retry_async(&$policy, attempts)
+                        .await
+                    {
+                        std::ops::ControlFlow::Continue(timeout) => {
+                            tokio::time::sleep(timeout).await;
+                            continue;
+                        }
+                        std::ops::ControlFlow::Break(_) => break res,
                     }
-                    std::ops::ControlFlow::Break(_) => break res,
-                },
+                }
             }
         }
     }};
@@ -74,7 +671,25 @@ macro_rules! retrying {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::{
+        retry_async, retry_invocation, AsyncRetryPolicy, Chain, CircuitBreaker, ErrorCodePolicy,
+        ExponentialBackoff, Fixed, JitteredBackoff, MaxDuration, OnRetry, RetryPolicy,
+        TransientErrorPolicy,
+    };
+    use crate::InvocationError;
+    use grammers_mtproto::mtp::RpcError;
+    use std::ops::ControlFlow;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    fn rpc_error(code: i32) -> InvocationError {
+        InvocationError::Rpc(RpcError {
+            code,
+            name: "TEST".to_string(),
+            value: None,
+            caused_by: None,
+        })
+    }
 
     struct Erroring {
         cnt: usize,
@@ -94,6 +709,25 @@ mod tests {
         }
     }
 
+    struct RpcErroring {
+        cnt: usize,
+        code: i32,
+    }
+
+    impl RpcErroring {
+        fn new(cnt: usize, code: i32) -> Self {
+            Self { cnt, code }
+        }
+
+        async fn run(&mut self) -> Result<usize, InvocationError> {
+            if self.cnt > 0 {
+                self.cnt -= 1;
+                return Err(rpc_error(self.code));
+            }
+            Ok(self.cnt)
+        }
+    }
+
     #[tokio::test]
     async fn test_retrying_macro_ok() {
         let policy = Fixed::new(10, Duration::new(0, 0));
@@ -111,4 +745,432 @@ mod tests {
         let r = retrying!(policy, err.run().await);
         assert!(r.is_err());
     }
+
+    fn delays(policy: &dyn RetryPolicy) -> Vec<Duration> {
+        (1..=5)
+            .map(|attempts| match policy.should_retry(attempts) {
+                ControlFlow::Continue(delay) => delay,
+                ControlFlow::Break(_) => panic!("policy should keep retrying"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_exponential_backoff_delays() {
+        let policy =
+            ExponentialBackoff::new(Duration::from_millis(100), 2.0, Duration::from_secs(1));
+
+        assert_eq!(
+            delays(&policy),
+            [100, 200, 400, 800, 1000].map(Duration::from_millis)
+        );
+    }
+
+    #[test]
+    fn test_exponential_backoff_unit_multiplier_is_fixed() {
+        let delay = Duration::from_millis(250);
+        let policy = ExponentialBackoff::new(delay, 1.0, Duration::from_secs(1));
+
+        assert_eq!(delays(&policy), delays(&Fixed::new(5, delay)));
+    }
+
+    #[test]
+    fn test_exponential_backoff_clamps_to_max_delay() {
+        let policy = ExponentialBackoff::new(Duration::from_secs(5), 2.0, Duration::from_secs(1));
+
+        assert_eq!(delays(&policy), [Duration::from_secs(1); 5]);
+    }
+
+    #[test]
+    fn test_exponential_backoff_does_not_overflow() {
+        let max_delay = Duration::from_secs(60);
+        let policy = ExponentialBackoff::new(Duration::from_secs(1), 10.0, max_delay);
+
+        assert_eq!(policy.delay_for(1_000), max_delay);
+        assert_eq!(policy.delay_for(usize::MAX), max_delay);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_exponential_backoff_rejects_nan_multiplier() {
+        ExponentialBackoff::new(Duration::from_secs(1), f64::NAN, Duration::from_secs(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_exponential_backoff_rejects_shrinking_multiplier() {
+        ExponentialBackoff::new(Duration::from_secs(1), 0.5, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_exponential_backoff_stops_after_max_attempts() {
+        let policy =
+            ExponentialBackoff::new(Duration::from_millis(100), 2.0, Duration::from_millis(300))
+                .max_attempts(5);
+
+        assert_eq!(
+            delays(&policy),
+            [100, 200, 300, 300, 300].map(Duration::from_millis)
+        );
+        assert!(policy.should_retry(6).is_break());
+    }
+
+    #[test]
+    fn test_jittered_backoff_stays_within_bounds() {
+        let delay = Duration::from_secs(1);
+        let policy = JitteredBackoff::with_seed(Fixed::new(5, delay), 42);
+
+        for jittered in delays(&policy) {
+            assert!(jittered >= delay / 2 && jittered <= delay * 3 / 2);
+        }
+        assert_eq!(policy.should_retry(6), ControlFlow::Break(()));
+
+        let policy = JitteredBackoff::with_seed(Fixed::new(5, delay), 42).with_jitter(0.1);
+        let delays = delays(&policy);
+        for jittered in delays.iter().copied() {
+            assert!(jittered >= delay.mul_f64(0.9) && jittered <= delay.mul_f64(1.1));
+        }
+        assert!(delays.iter().any(|&jittered| jittered < delay));
+        assert!(delays.iter().any(|&jittered| jittered > delay));
+    }
+
+    #[test]
+    fn test_jittered_backoff_without_jitter_is_inner() {
+        let delay = Duration::from_secs(1);
+        let policy = JitteredBackoff::new(Fixed::new(5, delay)).with_jitter(0.0);
+
+        assert_eq!(delays(&policy), delays(&Fixed::new(5, delay)));
+    }
+
+    #[test]
+    fn test_jittered_backoff_seed_is_deterministic() {
+        let delay = Duration::from_secs(1);
+        let a = JitteredBackoff::with_seed(Fixed::new(5, delay), 42);
+        let b = JitteredBackoff::with_seed(Fixed::new(5, delay), 42);
+
+        assert_eq!(delays(&a), delays(&b));
+    }
+
+    #[test]
+    fn test_jittered_backoff_spreads_simultaneous_clients() {
+        let delay = Duration::from_secs(1);
+        let runs = 100;
+        let different = (0..runs)
+            .filter(|_| {
+                let a = JitteredBackoff::new(Fixed::new(1, delay));
+                let b = JitteredBackoff::new(Fixed::new(1, delay));
+                a.should_retry(1) != b.should_retry(1)
+            })
+            .count();
+
+        assert!(different * 10 > runs * 9);
+    }
+
+    #[test]
+    fn test_max_duration_stops_after_budget() {
+        let delay = Duration::from_millis(20);
+        let policy = MaxDuration::new(delay, Box::new(Fixed::new(10, delay)));
+
+        // The budget is exactly one inner delay, so only one retry fits.
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        std::thread::sleep(delay);
+        assert_eq!(policy.should_retry(2), ControlFlow::Break(()));
+    }
+
+    #[test]
+    fn test_max_duration_respects_inner_policy() {
+        let delay = Duration::from_millis(1);
+        let policy = MaxDuration::new(Duration::from_secs(60), Box::new(Fixed::new(1, delay)));
+
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        assert_eq!(policy.should_retry(2), ControlFlow::Break(()));
+    }
+
+    #[test]
+    fn test_max_duration_does_not_wait_past_budget() {
+        let delay = Duration::from_secs(2);
+        let policy = MaxDuration::new(Duration::from_secs(1), Box::new(Fixed::new(10, delay)));
+
+        assert_eq!(policy.should_retry(1), ControlFlow::Break(()));
+    }
+
+    #[test]
+    fn test_max_duration_restarts_with_new_attempts() {
+        let delay = Duration::from_millis(20);
+        let policy = MaxDuration::new(delay, Box::new(Fixed::new(10, delay)));
+
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        std::thread::sleep(delay);
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+    }
+
+    #[test]
+    fn test_chain_switches_policies() {
+        let fast = Duration::from_millis(100);
+        let slow = Duration::from_secs(1);
+        let policy = Chain::new(Fixed::new(3, fast), 3, Fixed::new(2, slow));
+
+        let flow = (1..=6)
+            .map(|attempts| policy.should_retry(attempts))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            flow,
+            [
+                ControlFlow::Continue(fast),
+                ControlFlow::Continue(fast),
+                ControlFlow::Continue(fast),
+                ControlFlow::Continue(slow),
+                ControlFlow::Continue(slow),
+                ControlFlow::Break(()),
+            ]
+        );
+    }
+
+    struct Sleepy {
+        attempts: usize,
+        delay: Duration,
+    }
+
+    impl AsyncRetryPolicy for Sleepy {
+        async fn should_retry_async(&self, attempts: usize) -> ControlFlow<(), Duration> {
+            tokio::time::sleep(self.delay).await;
+            if attempts <= self.attempts {
+                ControlFlow::Continue(self.delay)
+            } else {
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retrying_async_macro_ok() {
+        let policy = Sleepy {
+            attempts: 10,
+            delay: Duration::from_millis(1),
+        };
+        let mut err = Erroring::new(5);
+
+        let r = retrying_async!(policy, err.run().await);
+        assert!(r.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_retrying_async_macro_err() {
+        let policy = Sleepy {
+            attempts: 3,
+            delay: Duration::from_millis(1),
+        };
+        let mut err = Erroring::new(5);
+
+        let r = retrying_async!(policy, err.run().await);
+        assert!(r.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retrying_async_macro_sync_policy() {
+        let policy: &dyn RetryPolicy = &Fixed::new(3, Duration::new(0, 0));
+        let mut err = Erroring::new(5);
+
+        let r = retrying_async!(policy, err.run().await);
+        assert!(r.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retry_async_function() {
+        let policy = Fixed::new(3, Duration::new(0, 0));
+        let mut calls = 0;
+
+        let r = retry_async(&policy, || {
+            calls += 1;
+            let call = calls;
+            async move {
+                if call <= 2 {
+                    Err(InvocationError::Dropped)
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await;
+        assert_eq!(r.unwrap(), 3);
+
+        let r: Result<(), _> = retry_async(&policy, || async { Err("failed") }).await;
+        assert_eq!(r, Err("failed"));
+    }
+
+    #[tokio::test]
+    async fn test_retry_invocation_function() {
+        let policy = TransientErrorPolicy::new(Box::new(Fixed::new(3, Duration::new(0, 0))));
+
+        let mut calls = 0;
+        let r = retry_invocation(&policy, || {
+            calls += 1;
+            let call = calls;
+            async move {
+                if call <= 2 {
+                    Err(rpc_error(500))
+                } else {
+                    Ok(call)
+                }
+            }
+        })
+        .await;
+        assert_eq!(r.unwrap(), 3);
+
+        let mut calls = 0;
+        let r: Result<(), _> = retry_invocation(&policy, || {
+            calls += 1;
+            async { Err(rpc_error(400)) }
+        })
+        .await;
+        assert!(r.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_error_code_policy_filters_codes() {
+        let delay = Duration::from_millis(1);
+        let policy = ErrorCodePolicy::new(&[500], Box::new(Fixed::new(3, delay)));
+
+        assert_eq!(
+            policy.should_retry_err(1, &rpc_error(500)),
+            ControlFlow::Continue(delay)
+        );
+        assert_eq!(
+            policy.should_retry_err(4, &rpc_error(500)),
+            ControlFlow::Break(())
+        );
+        assert_eq!(
+            policy.should_retry_err(1, &rpc_error(400)),
+            ControlFlow::Break(())
+        );
+        assert_eq!(policy.should_retry(1), ControlFlow::Break(()));
+    }
+
+    #[test]
+    fn test_transient_error_policy_gives_up_on_request_errors() {
+        let delay = Duration::from_millis(1);
+        let policy = TransientErrorPolicy::new(Box::new(Fixed::new(3, delay)));
+
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        assert_eq!(
+            policy.should_retry_err(1, &rpc_error(500)),
+            ControlFlow::Continue(delay)
+        );
+        assert_eq!(
+            policy.should_retry_err(1, &rpc_error(-503)),
+            ControlFlow::Continue(delay)
+        );
+        assert_eq!(policy.should_retry(4), ControlFlow::Break(()));
+        assert_eq!(
+            policy.should_retry_err(1, &rpc_error(401)),
+            ControlFlow::Break(())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retrying_macro_error_code() {
+        let policy = ErrorCodePolicy::new(&[500], Box::new(Fixed::new(10, Duration::new(0, 0))));
+
+        let mut err = RpcErroring::new(5, 500);
+        assert!(retrying!(policy, err.run().await).is_ok());
+
+        let mut err = RpcErroring::new(5, 400);
+        assert!(retrying!(policy, err.run().await).is_err());
+        assert_eq!(err.cnt, 4);
+    }
+
+    #[test]
+    fn test_transient_error_policy_waits_for_flood() {
+        let delay = Duration::from_millis(1);
+        let policy = TransientErrorPolicy::new(Box::new(Fixed::new(3, delay)));
+        let flood = InvocationError::Rpc(RpcError {
+            code: 420,
+            name: "FLOOD_WAIT".to_string(),
+            value: Some(2),
+            caused_by: None,
+        });
+
+        assert_eq!(
+            policy.should_retry_err(1, &flood),
+            ControlFlow::Continue(Duration::from_secs(2))
+        );
+        assert_eq!(policy.should_retry_err(4, &flood), ControlFlow::Break(()));
+    }
+
+    struct Counting;
+
+    impl RetryPolicy for Counting {
+        fn should_retry(&self, attempts: usize) -> ControlFlow<(), Duration> {
+            if attempts < 3 {
+                ControlFlow::Continue(Duration::new(0, 0))
+            } else {
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retrying_macro_attempts_only_policy() {
+        let mut err = RpcErroring::new(5, 400);
+        assert!(retrying!(Counting, err.run().await).is_err());
+        assert_eq!(err.cnt, 2);
+    }
+
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        let delay = Duration::from_millis(1);
+        let open_duration = Duration::from_millis(20);
+        let policy = CircuitBreaker::new(3, open_duration, Box::new(Fixed::new(10, delay)));
+
+        // Closed: failures are retried until the threshold is reached.
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        assert_eq!(policy.should_retry(2), ControlFlow::Continue(delay));
+        assert_eq!(policy.should_retry(3), ControlFlow::Break(()));
+
+        // Open: everything is given up immediately.
+        assert!(policy.is_open());
+        assert_eq!(policy.should_retry(1), ControlFlow::Break(()));
+
+        // Half-open: a single attempt is allowed through after the wait.
+        std::thread::sleep(open_duration);
+        assert!(!policy.is_open());
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+
+        // Closed: the attempt succeeded, so a new failure starts counting again.
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        assert_eq!(policy.should_retry(2), ControlFlow::Continue(delay));
+        assert!(!policy.is_open());
+    }
+
+    #[test]
+    fn test_circuit_breaker_reopens_on_failed_trial() {
+        let delay = Duration::from_millis(1);
+        let open_duration = Duration::from_millis(20);
+        let policy = CircuitBreaker::new(1, open_duration, Box::new(Fixed::new(10, delay)));
+
+        assert_eq!(policy.should_retry(1), ControlFlow::Break(()));
+        std::thread::sleep(open_duration);
+        assert_eq!(policy.should_retry(1), ControlFlow::Continue(delay));
+        assert_eq!(policy.should_retry(2), ControlFlow::Break(()));
+        assert!(policy.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_observes_every_continue() {
+        let delay = Duration::from_millis(1);
+        let observed = Mutex::new(Vec::new());
+        let policy = OnRetry::new(Fixed::new(3, delay), |attempts, delay| {
+            observed.lock().unwrap().push((attempts, delay))
+        });
+
+        let mut err = Erroring::new(5);
+        let r = retrying!(policy, err.run().await);
+        assert!(r.is_err());
+        assert_eq!(
+            *observed.lock().unwrap(),
+            [(1, delay), (2, delay), (3, delay)]
+        );
+    }
 }
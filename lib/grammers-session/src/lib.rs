@@ -6,6 +6,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 mod chat;
+// Not every generated method is needed by the session (e.g. `constructor_id`).
+#[allow(dead_code)]
 mod generated;
 mod message_box;
 
@@ -142,7 +144,7 @@ impl Session {
             .unwrap()
             .user
             .as_ref()
-            .map(|enums::User::User(user)| user.clone())
+            .map(|enums::User::User(user)| *user)
     }
 
     pub fn get_state(&self) -> Option<UpdateState> {
@@ -233,8 +233,8 @@ pub(super) fn adapt(
 fn message_peer(message: &tl::enums::Message) -> Option<tl::enums::Peer> {
     match message {
         tl::enums::Message::Empty(_) => None,
-        tl::enums::Message::Message(m) => Some(m.peer_id.clone()),
-        tl::enums::Message::Service(m) => Some(m.peer_id.clone()),
+        tl::enums::Message::Message(m) => Some(m.peer_id),
+        tl::enums::Message::Service(m) => Some(m.peer_id),
     }
 }
 
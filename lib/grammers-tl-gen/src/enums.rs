@@ -11,14 +11,11 @@
 use crate::grouper;
 use crate::metadata::Metadata;
 use crate::rustifier;
-use crate::{ignore_type, Config};
-use grammers_tl_parser::tl::{Definition, ParameterType, Type};
+use crate::{extra_derives, ignore_type, write_deprecated, Config, SkippedDerive};
+use grammers_tl_parser::tl::{Definition, Parameter, ParameterType, Type};
 use std::collections::HashSet;
 use std::io::{self, Write};
 
-/// Types that implement Copy from builtin_type
-const COPY_TYPES: [&str; 7] = ["bool", "f64", "i32", "i64", "u32", "[u8; 16]", "[u8; 32]"];
-
 /// Writes an enumeration listing all types such as the following rust code:
 ///
 /// ```ignore
@@ -32,12 +29,38 @@ fn write_enum<W: Write>(
     ty: &Type,
     metadata: &Metadata,
     config: &Config,
+    skipped: &mut Vec<SkippedDerive>,
 ) -> io::Result<()> {
     if config.impl_debug {
         writeln!(file, "{}#[derive(Debug)]", indent)?;
     }
 
-    writeln!(file, "{}#[derive(Clone, PartialEq)]", indent)?;
+    writeln!(
+        file,
+        "{}#[derive(Clone, {}PartialEq{})]",
+        indent,
+        if metadata.is_copy_type(ty) {
+            "Copy, "
+        } else {
+            ""
+        },
+        extra_derives(
+            config,
+            &rustifier::types::qual_name(ty),
+            metadata.is_eq_type(ty),
+            skipped,
+        ),
+    )?;
+    if config.serde_derive {
+        writeln!(
+            file,
+            "{}#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]",
+            indent
+        )?;
+    }
+    if config.non_exhaustive_enums {
+        writeln!(file, "{}#[non_exhaustive]", indent)?;
+    }
     writeln!(
         file,
         "{}pub enum {} {{",
@@ -45,6 +68,17 @@ fn write_enum<W: Write>(
         rustifier::types::type_name(ty)
     )?;
     for d in metadata.defs_with_type(ty) {
+        if config.serde_derive {
+            writeln!(
+                file,
+                "{}    #[cfg_attr(feature = \"serde\", serde(rename = \"{}\"))]",
+                indent,
+                d.full_name()
+            )?;
+        }
+        if config.mark_deprecated {
+            write_deprecated(file, &format!("{}    ", indent), d)?;
+        }
         write!(
             file,
             "{}    {}",
@@ -60,11 +94,11 @@ fn write_enum<W: Write>(
             write!(file, "(")?;
         }
 
-        if metadata.is_recursive_def(d) {
+        if metadata.is_boxed_def(d) {
             write!(file, "Box<")?;
         }
         write!(file, "{}", rustifier::definitions::qual_name(d))?;
-        if metadata.is_recursive_def(d) {
+        if metadata.is_boxed_def(d) {
             write!(file, ">")?;
         }
 
@@ -74,33 +108,15 @@ fn write_enum<W: Write>(
     Ok(())
 }
 
-/// Writes impl for getting common fields from enum variants
+/// Get the non-optional parameters which all of the definitions have in common.
 ///
-/// ```ignore
-/// enum Enum {
-///     A { id: i64, other: i64 },
-///     B { id: i64 }
-/// }
-///
-/// impl Enum {
-///     pub fn id(&self) -> i64 {
-///         self.id
-///     }
-/// }
-/// ```
-fn write_common_field_impl<W: Write>(
-    file: &mut W,
-    indent: &str,
-    ty: &Type,
-    metadata: &Metadata,
-    _config: &Config,
-) -> io::Result<()> {
+/// Nothing is considered common if there is only one definition.
+fn common_params<'a>(definitions: &[&'a Definition]) -> Vec<&'a Parameter> {
     // Don't generate if only one type
-    let definitions = metadata.defs_with_type(ty);
     if definitions.len() <= 1 {
-        return Ok(());
+        return Vec::new();
     }
-    // Get common parameters
+    // Get common parameters (only by name and type, descriptions may differ)
     let mut common_params = HashSet::new();
     for (i, d) in definitions.iter().enumerate() {
         // Filter out Options and flags parameters
@@ -113,10 +129,11 @@ fn write_common_field_impl<W: Write>(
                     !rustifier::parameters::qual_name(p).contains("Option<")
                 }
             })
+            .map(|p| (&p.name, &p.ty))
             .collect();
         // Faster
         if params.is_empty() {
-            return Ok(());
+            return Vec::new();
         }
         // Do intersection
         if i == 0 {
@@ -125,6 +142,36 @@ fn write_common_field_impl<W: Write>(
         }
         common_params = common_params.intersection(&params).copied().collect();
     }
+    definitions[0]
+        .params
+        .iter()
+        .filter(|p| common_params.contains(&(&p.name, &p.ty)))
+        .collect()
+}
+
+/// Writes impl for getting common fields from enum variants
+///
+/// ```ignore
+/// enum Enum {
+///     A { id: i64, other: i64 },
+///     B { id: i64 }
+/// }
+///
+/// impl Enum {
+///     pub fn id(&self) -> i64 {
+///         self.id
+///     }
+/// }
+/// ```
+fn write_common_field_impl<W: Write>(
+    file: &mut W,
+    indent: &str,
+    ty: &Type,
+    metadata: &Metadata,
+    _config: &Config,
+) -> io::Result<()> {
+    let definitions = metadata.defs_with_type(ty);
+    let common_params = common_params(definitions);
     if common_params.is_empty() {
         return Ok(());
     }
@@ -154,7 +201,7 @@ fn write_common_field_impl<W: Write>(
                 rustifier::definitions::variant_name(d),
                 rustifier::parameters::attr_name(param),
                 // Clone non Copy types
-                if COPY_TYPES.contains(&qual_name.as_ref()) {
+                if metadata.is_copy_param(param) {
                     ""
                 } else {
                     ".clone()"
@@ -167,6 +214,285 @@ fn write_common_field_impl<W: Write>(
     Ok(())
 }
 
+/// Writes impl for checking, borrowing and taking out the enum variants
+///
+/// ```ignore
+/// impl Enum {
+///     pub fn is_a(&self) -> bool {
+///         matches!(self, Self::A)
+///     }
+///
+///     pub fn as_b(&self) -> Option<&crate::types::B> {
+///         match self {
+///             Self::B(x) => Some(x),
+///             _ => None,
+///         }
+///     }
+///
+///     pub fn into_b(self) -> Option<crate::types::B> {
+///         match self {
+///             Self::B(x) => Some(x),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+fn write_variant_accessors<W: Write>(
+    file: &mut W,
+    indent: &str,
+    ty: &Type,
+    metadata: &Metadata,
+) -> io::Result<()> {
+    let definitions = metadata.defs_with_type(ty);
+    // Methods for common fields are defined too, so avoid any collisions
+    let common_params = common_params(definitions);
+    let collides = |name: &str| {
+        common_params
+            .iter()
+            .any(|p| rustifier::parameters::attr_name(p) == name)
+    };
+    let fallback = if definitions.len() > 1 {
+        format!("{}            _ => None,\n", indent)
+    } else {
+        String::new()
+    };
+
+    writeln!(
+        file,
+        "{}impl {} {{",
+        indent,
+        rustifier::types::type_name(ty)
+    )?;
+    for def in definitions {
+        let variant = rustifier::definitions::variant_name(def);
+        let accessor = rustifier::definitions::accessor_name(def);
+
+        if def.params.is_empty() {
+            let name = format!("is_{}", accessor);
+            if !collides(&name) {
+                writeln!(
+                    file,
+                    "{}    pub fn {}(&self) -> bool {{\n{}        matches!(self, Self::{})\n{}    }}",
+                    indent, name, indent, variant, indent,
+                )?;
+            }
+            continue;
+        }
+
+        let (as_value, into_value) = if metadata.is_boxed_def(def) {
+            ("x.as_ref()", "*x")
+        } else {
+            ("x", "x")
+        };
+        let qual_name = rustifier::definitions::qual_name(def);
+
+        let name = format!("as_{}", accessor);
+        if !collides(&name) {
+            writeln!(
+                file,
+                "{}    pub fn {}(&self) -> Option<&{}> {{\n{}        match self {{\n{}            Self::{}(x) => Some({}),\n{}{}        }}\n{}    }}",
+                indent, name, qual_name, indent, indent, variant, as_value, fallback, indent, indent,
+            )?;
+        }
+
+        let name = format!("into_{}", accessor);
+        if !collides(&name) {
+            writeln!(
+                file,
+                "{}    pub fn {}(self) -> Option<{}> {{\n{}        match self {{\n{}            Self::{}(x) => Some({}),\n{}{}        }}\n{}    }}",
+                indent, name, qual_name, indent, indent, variant, into_value, fallback, indent, indent,
+            )?;
+        }
+    }
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
+/// Writes impl for getting the constructor identifier of the enum variant
+///
+/// ```ignore
+/// impl Enum {
+///     pub fn constructor_id(&self) -> u32 {
+///         use crate::Identifiable;
+///         match self {
+///             Self::Variant(_) => crate::types::Name::CONSTRUCTOR_ID,
+///         }
+///     }
+/// }
+/// ```
+fn write_constructor_id<W: Write>(
+    file: &mut W,
+    indent: &str,
+    ty: &Type,
+    metadata: &Metadata,
+) -> io::Result<()> {
+    writeln!(
+        file,
+        "{}impl {} {{",
+        indent,
+        rustifier::types::type_name(ty)
+    )?;
+    writeln!(file, "{}    pub fn constructor_id(&self) -> u32 {{", indent)?;
+    writeln!(file, "{}        use crate::Identifiable;", indent)?;
+    writeln!(file, "{}        match self {{", indent)?;
+    for d in metadata.defs_with_type(ty) {
+        writeln!(
+            file,
+            "{}            Self::{}{} => {}::CONSTRUCTOR_ID,",
+            indent,
+            rustifier::definitions::variant_name(d),
+            if d.params.is_empty() { "" } else { "(_)" },
+            rustifier::definitions::qual_name(d)
+        )?;
+    }
+    writeln!(file, "{}        }}", indent)?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
+/// Writes impl for getting the name of the TL definition of the enum variant
+///
+/// ```ignore
+/// impl Enum {
+///     pub fn name(&self) -> &'static str {
+///         match self {
+///             Self::Variant(_) => "ns.name",
+///         }
+///     }
+/// }
+/// ```
+fn write_variant_name<W: Write>(
+    file: &mut W,
+    indent: &str,
+    ty: &Type,
+    metadata: &Metadata,
+) -> io::Result<()> {
+    let definitions = metadata.defs_with_type(ty);
+    // Methods for common fields are defined too, so avoid any collisions
+    if common_params(definitions)
+        .iter()
+        .any(|p| rustifier::parameters::attr_name(p) == "name")
+    {
+        return Ok(());
+    }
+
+    writeln!(
+        file,
+        "{}impl {} {{",
+        indent,
+        rustifier::types::type_name(ty)
+    )?;
+    writeln!(file, "{}    pub fn name(&self) -> &'static str {{", indent)?;
+    writeln!(file, "{}        match self {{", indent)?;
+    for d in definitions {
+        writeln!(
+            file,
+            "{}            Self::{}{} => \"{}\",",
+            indent,
+            rustifier::definitions::variant_name(d),
+            if d.params.is_empty() { "" } else { "(_)" },
+            d.full_name()
+        )?;
+    }
+    writeln!(file, "{}        }}", indent)?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
+/// Writes impl for displaying the name and constructor identifier of the enum variant
+///
+/// ```ignore
+/// impl std::fmt::Display for Enum {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         match self {
+///             Self::Variant(_) => f.write_str("EnumVariant(0x12345678)"),
+///         }
+///     }
+/// }
+/// ```
+fn write_impl_display<W: Write>(
+    file: &mut W,
+    indent: &str,
+    ty: &Type,
+    metadata: &Metadata,
+) -> io::Result<()> {
+    writeln!(
+        file,
+        "{}impl std::fmt::Display for {} {{",
+        indent,
+        rustifier::types::type_name(ty)
+    )?;
+    writeln!(
+        file,
+        "{}    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{",
+        indent
+    )?;
+    writeln!(file, "{}        match self {{", indent)?;
+    for d in metadata.defs_with_type(ty) {
+        writeln!(
+            file,
+            "{}            Self::{}{} => f.write_str(\"{}(0x{:08x})\"),",
+            indent,
+            rustifier::definitions::variant_name(d),
+            if d.params.is_empty() { "" } else { "(_)" },
+            rustifier::definitions::type_name(d),
+            d.id
+        )?;
+    }
+    writeln!(file, "{}        }}", indent)?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
+/// Writes a constant listing the constructor identifiers of every variant of the enum,
+/// in the same order they are matched when deserializing
+///
+/// ```ignore
+/// impl Enum {
+///     pub const VARIANT_IDS: &'static [u32] = {
+///         use crate::Identifiable;
+///         &[
+///             crate::types::Variant::CONSTRUCTOR_ID,
+///         ]
+///     };
+/// }
+/// ```
+fn write_variant_ids<W: Write>(
+    file: &mut W,
+    indent: &str,
+    ty: &Type,
+    metadata: &Metadata,
+) -> io::Result<()> {
+    writeln!(
+        file,
+        "{}impl {} {{",
+        indent,
+        rustifier::types::type_name(ty)
+    )?;
+    writeln!(
+        file,
+        "{}    pub const VARIANT_IDS: &'static [u32] = {{",
+        indent
+    )?;
+    writeln!(file, "{}        use crate::Identifiable;", indent)?;
+    writeln!(file, "{}        &[", indent)?;
+    for d in metadata.defs_with_type(ty) {
+        writeln!(
+            file,
+            "{}            {}::CONSTRUCTOR_ID,",
+            indent,
+            rustifier::definitions::qual_name(d)
+        )?;
+    }
+    writeln!(file, "{}        ]", indent)?;
+    writeln!(file, "{}    }};", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
 /// Defines the `impl Serializable` corresponding to the type definitions:
 ///
 /// ```ignore
@@ -180,6 +506,12 @@ fn write_common_field_impl<W: Write>(
 ///             },
 ///         }
 ///     }
+///
+///     fn serialized_len(&self) -> usize {
+///         match self {
+///             Self::Variant(x) => 4 + x.serialized_len(),
+///         }
+///     }
 /// }
 /// ```
 fn write_serializable<W: Write>(
@@ -223,6 +555,28 @@ fn write_serializable<W: Write>(
     }
     writeln!(file, "{}        }}", indent)?;
     writeln!(file, "{}    }}", indent)?;
+
+    writeln!(file, "{}    fn serialized_len(&self) -> usize {{", indent)?;
+    writeln!(file, "{}        match self {{", indent)?;
+    for d in metadata.defs_with_type(ty) {
+        if d.params.is_empty() {
+            writeln!(
+                file,
+                "{}            Self::{} => 4,",
+                indent,
+                rustifier::definitions::variant_name(d),
+            )?;
+        } else {
+            writeln!(
+                file,
+                "{}            Self::{}(x) => 4 + x.serialized_len(),",
+                indent,
+                rustifier::definitions::variant_name(d),
+            )?;
+        }
+    }
+    writeln!(file, "{}        }}", indent)?;
+    writeln!(file, "{}    }}", indent)?;
     writeln!(file, "{}}}", indent)?;
     Ok(())
 }
@@ -276,7 +630,7 @@ fn write_deserializable<W: Write>(
             write!(file, "(")?;
         }
 
-        if metadata.is_recursive_def(d) {
+        if metadata.is_boxed_def(d) {
             write!(file, "Box::new(")?;
         }
         write!(
@@ -284,7 +638,7 @@ fn write_deserializable<W: Write>(
             "{}::deserialize(buf)?",
             rustifier::definitions::qual_name(d)
         )?;
-        if metadata.is_recursive_def(d) {
+        if metadata.is_boxed_def(d) {
             write!(file, ")")?;
         }
         writeln!(file, "),")?;
@@ -292,8 +646,8 @@ fn write_deserializable<W: Write>(
     writeln!(
         file,
         "{}            _ => return Err(\
-         crate::deserialize::Error::UnexpectedConstructor {{ id }}),",
-        indent
+         crate::deserialize::Error::UnexpectedConstructor {{ id, expected: \"{}\" }}),",
+        indent, ty
     )?;
     writeln!(file, "{}        }})", indent)?;
     writeln!(file, "{}    }}", indent)?;
@@ -338,7 +692,7 @@ fn write_impl_from<W: Write>(
 
         if def.params.is_empty() {
             writeln!(file)?;
-        } else if metadata.is_recursive_def(def) {
+        } else if metadata.is_boxed_def(def) {
             writeln!(file, "(Box::new(x))")?;
         } else {
             writeln!(file, "(x)")?;
@@ -357,9 +711,23 @@ fn write_definition<W: Write>(
     ty: &Type,
     metadata: &Metadata,
     config: &Config,
+    skipped: &mut Vec<SkippedDerive>,
 ) -> io::Result<()> {
-    write_enum(file, indent, ty, metadata, config)?;
+    write_enum(file, indent, ty, metadata, config, skipped)?;
     write_common_field_impl(file, indent, ty, metadata, config)?;
+    if config.gen_variant_accessors {
+        write_variant_accessors(file, indent, ty, metadata)?;
+    }
+    write_constructor_id(file, indent, ty, metadata)?;
+    if config.gen_variant_names {
+        write_variant_name(file, indent, ty, metadata)?;
+    }
+    if config.gen_variant_ids {
+        write_variant_ids(file, indent, ty, metadata)?;
+    }
+    if config.impl_display {
+        write_impl_display(file, indent, ty, metadata)?;
+    }
     write_serializable(file, indent, ty, metadata)?;
     write_deserializable(file, indent, ty, metadata)?;
     if config.impl_from_type {
@@ -374,6 +742,7 @@ pub(crate) fn write_enums_mod<W: Write>(
     definitions: &[Definition],
     metadata: &Metadata,
     config: &Config,
+    skipped: &mut Vec<SkippedDerive>,
 ) -> io::Result<()> {
     // Begin outermost mod
     write!(
@@ -386,9 +755,12 @@ pub(crate) fn write_enums_mod<W: Write>(
          /// [`Serializable`]: /grammers_tl_types/trait.Serializable.html\n\
          /// [`Deserializable`]: /grammers_tl_types/trait.Deserializable.html\n\
          #[allow(clippy::large_enum_variant)]\n\
-         pub mod enums {{\n\
          "
     )?;
+    if config.mark_deprecated {
+        writeln!(file, "#[allow(deprecated)]")?;
+    }
+    writeln!(file, "pub mod enums {{")?;
 
     let grouped = grouper::group_types_by_ns(definitions);
     let mut sorted_keys: Vec<&Option<String>> = grouped.keys().collect();
@@ -404,7 +776,7 @@ pub(crate) fn write_enums_mod<W: Write>(
         };
 
         for ty in grouped[key].iter().filter(|ty| !ignore_type(ty)) {
-            write_definition(&mut file, indent, ty, metadata, config)?;
+            write_definition(&mut file, indent, ty, metadata, config, skipped)?;
         }
 
         // End possibly inner mod
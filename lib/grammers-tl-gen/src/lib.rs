@@ -11,18 +11,36 @@
 mod enums;
 mod grouper;
 mod metadata;
+mod prelude;
 mod rustifier;
 mod structs;
 
 use grammers_tl_parser::tl::{Category, Definition, Type};
+use std::collections::HashMap;
 use std::io::{self, Write};
 
 pub struct Config {
     pub gen_name_for_id: bool,
     pub deserializable_functions: bool,
     pub impl_debug: bool,
+    pub impl_display: bool,
     pub impl_from_type: bool,
     pub impl_from_enum: bool,
+    pub serde_derive: bool,
+    pub impl_default: bool,
+    pub gen_builders: bool,
+    pub gen_constructors: bool,
+    pub gen_doc_comments: bool,
+    pub gen_variant_accessors: bool,
+    pub gen_variant_names: bool,
+    pub gen_variant_ids: bool,
+    pub gen_id_for_name: bool,
+    pub non_exhaustive_enums: bool,
+    pub extra_derives: Vec<String>,
+    pub impl_hash_eq: bool,
+    pub box_variant_threshold: Option<usize>,
+    pub mark_deprecated: bool,
+    pub gen_prelude: bool,
 }
 
 impl Default for Config {
@@ -31,8 +49,24 @@ impl Default for Config {
             gen_name_for_id: false,
             deserializable_functions: false,
             impl_debug: true,
+            impl_display: false,
             impl_from_type: true,
             impl_from_enum: true,
+            serde_derive: false,
+            impl_default: false,
+            gen_builders: false,
+            gen_constructors: false,
+            gen_doc_comments: false,
+            gen_variant_accessors: false,
+            gen_variant_names: false,
+            gen_variant_ids: false,
+            gen_id_for_name: false,
+            non_exhaustive_enums: false,
+            extra_derives: Vec::new(),
+            impl_hash_eq: false,
+            box_variant_threshold: None,
+            mark_deprecated: false,
+            gen_prelude: false,
         }
     }
 }
@@ -45,12 +79,98 @@ fn ignore_type(ty: &Type) -> bool {
     SPECIAL_CASED_TYPES.iter().any(|&x| x == ty.name)
 }
 
+/// Derives which can only be used on types whose fields are all `Eq` and `Hash`.
+const EQ_DERIVES: [&str; 3] = ["Eq", "Hash", "Ord"];
+
+/// Writes the `#[deprecated]` attribute for the definition if it is marked as deprecated.
+fn write_deprecated<W: Write>(file: &mut W, indent: &str, def: &Definition) -> io::Result<()> {
+    match def.deprecated.as_deref() {
+        Some("") => writeln!(file, "{}#[deprecated]", indent),
+        Some(note) => writeln!(file, "{}#[deprecated(note = {:?})]", indent, note),
+        None => Ok(()),
+    }
+}
+
+/// A derive from `Config::extra_derives` which was not applied to a type, because one of its
+/// fields can't implement it (such as a `f64` preventing `Eq` and `Hash`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SkippedDerive {
+    /// The name of the derive that was skipped.
+    pub derive: String,
+    /// The fully-qualified name of the type that was generated without it.
+    pub type_name: String,
+}
+
+/// Format the derives to append after the base derives of the type `name`.
+///
+/// `Eq` and `Hash` are included for types that can have them if `Config::impl_hash_eq`
+/// is set. Any `Config::extra_derives` the type can't satisfy are added to `skipped` instead.
+fn extra_derives(
+    config: &Config,
+    name: &str,
+    eq: bool,
+    skipped: &mut Vec<SkippedDerive>,
+) -> String {
+    let mut result = String::new();
+    let hash_eq = config.impl_hash_eq && eq;
+    if hash_eq {
+        result.push_str(", Eq, Hash");
+    }
+    for derive in config.extra_derives.iter() {
+        if hash_eq && (derive == "Eq" || derive == "Hash") {
+            continue;
+        }
+        if !eq && EQ_DERIVES.contains(&derive.as_str()) {
+            skipped.push(SkippedDerive {
+                derive: derive.clone(),
+                type_name: name.to_string(),
+            });
+            continue;
+        }
+        result.push_str(", ");
+        result.push_str(derive);
+    }
+    result
+}
+
+/// Make sure no two definitions share the same identifier, or the generated
+/// code would silently deserialize one of them as the other.
+fn check_duplicate_ids(definitions: &[Definition]) -> io::Result<()> {
+    let mut seen = HashMap::new();
+    let mut duplicates = Vec::new();
+    for def in definitions {
+        if let Some(prev) = seen.insert(def.id, def) {
+            duplicates.push(format!(
+                "{:08x} ({} and {})",
+                def.id,
+                prev.full_name(),
+                def.full_name()
+            ));
+        }
+    }
+
+    if duplicates.is_empty() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("duplicate constructor ids: {}", duplicates.join(", ")),
+        ))
+    }
+}
+
+/// Write the Rust code for the given definitions into `file`.
+///
+/// Returns the `Config::extra_derives` which could not be applied to some of the generated types,
+/// so that the caller can decide how to report them (for example, as `cargo:warning` lines).
 pub fn generate_rust_code(
     file: &mut impl Write,
     definitions: &[Definition],
     layer: i32,
     config: &Config,
-) -> io::Result<()> {
+) -> io::Result<Vec<SkippedDerive>> {
+    check_duplicate_ids(definitions)?;
+
     writeln!(
         file,
         r#"
@@ -69,32 +189,81 @@ pub const LAYER: i32 = {};
     )?;
 
     if config.gen_name_for_id {
+        // A sorted table is used instead of a `match` because it's much faster to compile.
+        let mut names = definitions
+            .iter()
+            .map(|def| (def.id, def.full_name()))
+            .collect::<Vec<_>>();
+        names.push((0x1cb5c415, "vector".to_string()));
+        names.sort_unstable();
+
         writeln!(
             file,
             r#"
 /// Return the name from the `.tl` definition corresponding to the provided definition identifier.
 pub fn name_for_id(id: u32) -> &'static str {{
-    match id {{
-        0x1cb5c415 => "vector","#
+    match NAMES.binary_search_by_key(&id, |&(id, _)| id) {{
+        Ok(i) => NAMES[i].1,
+        Err(_) => "(unknown)",
+    }}
+}}
+
+static NAMES: &[(u32, &str)] = &["#
         )?;
-        for def in definitions {
-            writeln!(file, r#"        0x{:x} => "{}","#, def.id, def.full_name())?;
+        for (id, name) in names {
+            writeln!(file, r#"    (0x{:x}, "{}"),"#, id, name)?;
         }
+        writeln!(file, "];")?;
+    }
+
+    if config.gen_id_for_name {
+        let mut ids = definitions
+            .iter()
+            .map(|def| (def.full_name(), def.id))
+            .collect::<Vec<_>>();
+        ids.push(("vector".to_string(), 0x1cb5c415));
+        ids.sort_unstable();
 
         writeln!(
             file,
             r#"
-        _ => "(unknown)",
-    }}
+/// Return the definition identifier corresponding to the provided name from the `.tl` definition.
+pub fn id_for_name(name: &str) -> Option<u32> {{
+    IDS.binary_search_by_key(&name, |&(name, _)| name)
+        .ok()
+        .map(|i| IDS[i].1)
 }}
-    "#,
+
+static IDS: &[(&str, u32)] = &["#
         )?;
+        for (name, id) in ids {
+            writeln!(file, r#"    ("{}", 0x{:x}),"#, name, id)?;
+        }
+        writeln!(file, "];")?;
     }
 
-    let metadata = metadata::Metadata::new(definitions);
-    structs::write_category_mod(file, Category::Types, definitions, &metadata, config)?;
-    structs::write_category_mod(file, Category::Functions, definitions, &metadata, config)?;
-    enums::write_enums_mod(file, definitions, &metadata, config)?;
+    let mut skipped = Vec::new();
+    let metadata = metadata::Metadata::new(definitions, config.box_variant_threshold);
+    structs::write_category_mod(
+        file,
+        Category::Types,
+        definitions,
+        &metadata,
+        config,
+        &mut skipped,
+    )?;
+    structs::write_category_mod(
+        file,
+        Category::Functions,
+        definitions,
+        &metadata,
+        config,
+        &mut skipped,
+    )?;
+    enums::write_enums_mod(file, definitions, &metadata, config, &mut skipped)?;
+    if config.gen_prelude {
+        prelude::write_prelude_mod(file, definitions, config)?;
+    }
 
-    Ok(())
+    Ok(skipped)
 }
@@ -7,19 +7,39 @@
 // except according to those terms.
 use std::collections::{HashMap, HashSet};
 
+use crate::rustifier;
 use grammers_tl_parser::tl::{Category, Definition, Parameter, ParameterType, Type};
 
+/// Types that implement Copy from builtin_type
+pub(crate) const COPY_TYPES: [&str; 7] =
+    ["bool", "f64", "i32", "i64", "u32", "[u8; 16]", "[u8; 32]"];
+
+/// Types from builtin_type that don't implement Eq (nor Hash)
+pub(crate) const NON_EQ_TYPES: [&str; 1] = ["f64"];
+
+/// Size of a pointer, as used when estimating the size of the generated types.
+const POINTER_SIZE: usize = 8;
+
+/// Size of a `String` or `Vec`, as used when estimating the size of the generated types.
+const BUFFER_SIZE: usize = 3 * POINTER_SIZE;
+
 /// Additional metadata required by several parts of the generation.
 pub(crate) struct Metadata<'a> {
     recursing_defs: HashSet<u32>,
+    boxed_defs: HashSet<u32>,
+    copy_defs: HashSet<u32>,
+    eq_defs: HashSet<u32>,
     defs_with_type: HashMap<(&'a Vec<String>, &'a String), Vec<&'a Definition>>,
     unused_flags: HashMap<(&'a Vec<String>, &'a String), Vec<&'a Parameter>>,
 }
 
 impl<'a> Metadata<'a> {
-    pub fn new(definitions: &'a [Definition]) -> Self {
+    pub fn new(definitions: &'a [Definition], box_variant_threshold: Option<usize>) -> Self {
         let mut metadata = Self {
             recursing_defs: HashSet::new(),
+            boxed_defs: HashSet::new(),
+            copy_defs: HashSet::new(),
+            eq_defs: HashSet::new(),
             defs_with_type: HashMap::new(),
             unused_flags: HashMap::new(),
         };
@@ -63,6 +83,67 @@ impl<'a> Metadata<'a> {
             }
         });
 
+        metadata.boxed_defs = match box_variant_threshold {
+            Some(threshold) => {
+                let mut estimator = SizeEstimator {
+                    defs_with_type: &metadata.defs_with_type,
+                    recursing_defs: &metadata.recursing_defs,
+                    threshold,
+                    def_sizes: HashMap::new(),
+                    visiting: HashSet::new(),
+                };
+                type_definitions
+                    .iter()
+                    .filter(|d| estimator.is_boxed(d))
+                    .map(|d| d.id)
+                    .collect()
+            }
+            None => metadata.recursing_defs.clone(),
+        };
+
+        // Start assuming every definition can be `Copy`, and remove those
+        // with fields that can't until nothing changes. Recursive definitions
+        // are `Box`ed, so they're never `Copy` and can't make this loop forever.
+        metadata.copy_defs = definitions.iter().map(|d| d.id).collect();
+        loop {
+            let non_copy = definitions
+                .iter()
+                .filter(|d| {
+                    metadata.copy_defs.contains(&d.id)
+                        && !d.params.iter().all(|p| metadata.is_copy_param(p))
+                })
+                .map(|d| d.id)
+                .collect::<Vec<_>>();
+
+            if non_copy.is_empty() {
+                break;
+            }
+            non_copy.iter().for_each(|id| {
+                metadata.copy_defs.remove(id);
+            });
+        }
+
+        // Same as above, but for `Eq`. Here recursive definitions are fine, since
+        // `Box<T>` is `Eq` as long as `T` is.
+        metadata.eq_defs = definitions.iter().map(|d| d.id).collect();
+        loop {
+            let non_eq = definitions
+                .iter()
+                .filter(|d| {
+                    metadata.eq_defs.contains(&d.id)
+                        && !d.params.iter().all(|p| metadata.is_eq_param(p))
+                })
+                .map(|d| d.id)
+                .collect::<Vec<_>>();
+
+            if non_eq.is_empty() {
+                break;
+            }
+            non_eq.iter().for_each(|id| {
+                metadata.eq_defs.remove(id);
+            });
+        }
+
         metadata
     }
 
@@ -73,10 +154,97 @@ impl<'a> Metadata<'a> {
             .unwrap_or(false)
     }
 
-    /// Returns `true` if any of the parameters of `Definition` eventually
-    /// contains the same type as the `Definition` itself (meaning it recurses).
-    pub fn is_recursive_def(&self, def: &Definition) -> bool {
-        self.recursing_defs.contains(&def.id)
+    /// Returns `true` if the variant for the `Definition` is stored in a `Box`, either because
+    /// any of its parameters eventually contains the same type as the `Definition` itself
+    /// (meaning it recurses), or because it's estimated to be larger than the configured
+    /// threshold.
+    pub fn is_boxed_def(&self, def: &Definition) -> bool {
+        self.boxed_defs.contains(&def.id)
+    }
+
+    /// Returns `true` if the `Definition` has parameters and all of them are
+    /// conditional on some flag (so it has a sensible default value).
+    pub fn is_all_optional_def(&self, def: &Definition) -> bool {
+        let mut normal_params = def.params.iter().filter_map(|p| match &p.ty {
+            ParameterType::Flags => None,
+            ParameterType::Normal { flag, .. } => Some(flag),
+        });
+
+        let mut any = false;
+        let all = normal_params.all(|flag| {
+            any = true;
+            flag.is_some()
+        });
+        any && all
+    }
+
+    /// Returns `true` if all of the fields of the `Definition` are `Copy`.
+    pub fn is_copy_def(&self, def: &Definition) -> bool {
+        self.copy_defs.contains(&def.id)
+    }
+
+    /// Returns `true` if all of the variants of the boxed `Type` are `Copy`.
+    pub fn is_copy_type(&self, ty: &Type) -> bool {
+        match self.defs_with_type.get(&(&ty.namespace, &ty.name)) {
+            Some(defs) => defs
+                .iter()
+                .all(|d| self.is_copy_def(d) && !self.is_boxed_def(d)),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the field for the `Parameter` is `Copy`.
+    pub fn is_copy_param(&self, param: &Parameter) -> bool {
+        match &param.ty {
+            ParameterType::Flags => true,
+            ParameterType::Normal { ty, .. } => {
+                if ty.generic_ref || ty.generic_arg.is_some() {
+                    false
+                } else if COPY_TYPES.contains(&rustifier::types::qual_name(ty).as_ref()) {
+                    true
+                } else {
+                    // Bare references to other definitions are rare enough to not bother.
+                    !ty.bare && self.is_copy_type(ty)
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if all of the fields of the `Definition` are `Eq` and `Hash`.
+    pub fn is_eq_def(&self, def: &Definition) -> bool {
+        self.eq_defs.contains(&def.id)
+    }
+
+    /// Returns `true` if all of the variants of the boxed `Type` are `Eq` and `Hash`.
+    pub fn is_eq_type(&self, ty: &Type) -> bool {
+        match self.defs_with_type.get(&(&ty.namespace, &ty.name)) {
+            Some(defs) => defs.iter().all(|d| self.is_eq_def(d)),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the field for the `Parameter` is `Eq` and `Hash`.
+    pub fn is_eq_param(&self, param: &Parameter) -> bool {
+        match &param.ty {
+            ParameterType::Flags => true,
+            ParameterType::Normal { ty, .. } => self.is_eq_field_type(ty),
+        }
+    }
+
+    fn is_eq_field_type(&self, ty: &Type) -> bool {
+        if ty.generic_ref {
+            // The derive will require the generic parameter to be `Eq` itself.
+            true
+        } else if let Some(arg) = &ty.generic_arg {
+            self.is_eq_field_type(arg)
+        } else if NON_EQ_TYPES.contains(&rustifier::types::qual_name(ty).as_ref()) {
+            false
+        } else if self.defs_with_type.contains_key(&(&ty.namespace, &ty.name)) {
+            self.is_eq_type(ty)
+        } else {
+            // Other builtin types (integers, strings, bytes...) are all `Eq`.
+            true
+        }
     }
 
     pub fn defs_with_type(&self, ty: &'a Type) -> &Vec<&Definition> {
@@ -84,6 +252,91 @@ impl<'a> Metadata<'a> {
     }
 }
 
+/// Estimates how large the generated types will be, in order to decide which enum variants
+/// to `Box`. The layout the compiler picks can differ (padding, niches), but it's close enough.
+struct SizeEstimator<'a, 'm> {
+    defs_with_type: &'m HashMap<(&'a Vec<String>, &'a String), Vec<&'a Definition>>,
+    recursing_defs: &'m HashSet<u32>,
+    threshold: usize,
+    def_sizes: HashMap<u32, usize>,
+    visiting: HashSet<u32>,
+}
+
+impl SizeEstimator<'_, '_> {
+    fn is_boxed(&mut self, def: &Definition) -> bool {
+        !def.params.is_empty()
+            && (self.recursing_defs.contains(&def.id) || self.def_size(def) > self.threshold)
+    }
+
+    fn def_size(&mut self, def: &Definition) -> usize {
+        if let Some(size) = self.def_sizes.get(&def.id) {
+            return *size;
+        }
+        if !self.visiting.insert(def.id) {
+            // Only reachable through a recursive definition, which is boxed anyway.
+            return POINTER_SIZE;
+        }
+
+        let size = def.params.iter().map(|p| self.param_size(p)).sum();
+        self.visiting.remove(&def.id);
+        self.def_sizes.insert(def.id, size);
+        size
+    }
+
+    fn param_size(&mut self, param: &Parameter) -> usize {
+        match &param.ty {
+            ParameterType::Flags => 4,
+            ParameterType::Normal { ty, flag } if flag.is_some() && ty.name == "true" => 1,
+            ParameterType::Normal { ty, flag } => {
+                let size = self.type_size(ty);
+                let has_niche =
+                    ty.generic_arg.is_some() || ty.name == "bytes" || ty.name == "string";
+                if flag.is_some() && !has_niche {
+                    size + POINTER_SIZE
+                } else {
+                    size
+                }
+            }
+        }
+    }
+
+    fn type_size(&mut self, ty: &Type) -> usize {
+        if ty.generic_ref {
+            return POINTER_SIZE;
+        }
+        if ty.generic_arg.is_some() {
+            return BUFFER_SIZE;
+        }
+
+        match ty.name.as_ref() {
+            "Bool" | "true" => 1,
+            "int" => 4,
+            "long" | "double" => 8,
+            "int128" => 16,
+            "int256" => 32,
+            "bytes" | "string" => BUFFER_SIZE,
+            _ => match self.defs_with_type.get(&(&ty.namespace, &ty.name)) {
+                Some(defs) => {
+                    let largest = defs
+                        .iter()
+                        .map(|d| {
+                            if self.is_boxed(d) {
+                                POINTER_SIZE
+                            } else {
+                                self.def_size(d)
+                            }
+                        })
+                        .max()
+                        .unwrap_or(0);
+                    // Room for the discriminant.
+                    largest + POINTER_SIZE
+                }
+                None => POINTER_SIZE,
+            },
+        }
+    }
+}
+
 fn def_self_references(
     root: &Definition,
     check: &Definition,
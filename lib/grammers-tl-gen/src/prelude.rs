@@ -0,0 +1,74 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Code to generate a `prelude` re-exporting the most commonly used items.
+
+use crate::grouper;
+use crate::rustifier;
+use crate::{ignore_type, Config};
+use grammers_tl_parser::tl::{Category, Definition};
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/// Boxed types whose constructors are re-exported too, since they are needed to refer to peers.
+const PRELUDE_CONSTRUCTOR_TYPES: [&str; 3] = ["InputPeer", "InputUser", "InputChannel"];
+
+/// Writes the `prelude` module, re-exporting the enums without a namespace, along with the
+/// constructors of the `InputPeer` family whose names don't clash with those enums.
+///
+/// Namespaced enums are left out, because their names often clash with the ones without a
+/// namespace (for example, `enums::photos::Photo` and `enums::Photo`).
+pub(crate) fn write_prelude_mod<W: Write>(
+    file: &mut W,
+    definitions: &[Definition],
+    config: &Config,
+) -> io::Result<()> {
+    writeln!(
+        file,
+        "\
+         /// Re-exports the most commonly used types at a shallow path, meant to be\n\
+         /// glob-imported. This includes every boxed type without a namespace, along\n\
+         /// with the constructors of `InputPeer`, `InputUser` and `InputChannel`."
+    )?;
+    if config.mark_deprecated {
+        writeln!(file, "#[allow(deprecated)]")?;
+    }
+    writeln!(file, "pub mod prelude {{")?;
+
+    let grouped = grouper::group_types_by_ns(definitions);
+    let enum_names = grouped
+        .get(&None)
+        .into_iter()
+        .flatten()
+        .filter(|ty| !ignore_type(ty))
+        .map(|ty| rustifier::types::type_name(ty))
+        .collect::<Vec<_>>();
+
+    for name in enum_names.iter() {
+        writeln!(file, "    pub use crate::enums::{};", name)?;
+    }
+
+    let taken = enum_names.iter().collect::<HashSet<_>>();
+    let mut constructors = definitions
+        .iter()
+        .filter(|def| {
+            def.category == Category::Types
+                && def.namespace.is_empty()
+                && PRELUDE_CONSTRUCTOR_TYPES.contains(&def.ty.name.as_str())
+        })
+        .map(rustifier::definitions::type_name)
+        .filter(|name| !taken.contains(name))
+        .collect::<Vec<_>>();
+    constructors.sort();
+
+    for name in constructors {
+        writeln!(file, "    pub use crate::types::{};", name)?;
+    }
+
+    writeln!(file, "}}")
+}
@@ -120,6 +120,25 @@ pub mod definitions {
         }
         .to_string()
     }
+
+    /// Get the name to use as the suffix of the methods accessing the variant.
+    ///
+    /// For example, transforms the variant `PeerSelf` into `peer_self`.
+    pub fn accessor_name(def: &Definition) -> String {
+        let variant = variant_name(def);
+        let mut result = String::with_capacity(variant.len() + 2);
+        for (i, c) in variant.chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                if i != 0 {
+                    result.push('_');
+                }
+                result.push(c.to_ascii_lowercase());
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
 }
 
 pub mod types {
@@ -234,18 +253,24 @@ pub mod parameters {
         }
     }
 
+    /// Keywords which can't be used as identifiers, but can be used as raw identifiers.
+    const RUST_KEYWORDS: [&str; 47] = [
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while",
+    ];
+
     pub fn attr_name(param: &Parameter) -> String {
-        match &param.name[..] {
-            "final" => "r#final".into(),
-            "loop" => "r#loop".into(),
+        let mut result = param.name.clone();
+        result[..].make_ascii_lowercase();
+        match &result[..] {
+            // Can't be raw identifiers.
             "self" => "is_self".into(),
-            "static" => "r#static".into(),
-            "type" => "r#type".into(),
-            _ => {
-                let mut result = param.name.clone();
-                result[..].make_ascii_lowercase();
-                result
-            }
+            "crate" | "super" => format!("{}_", result),
+            name if RUST_KEYWORDS.contains(&name) => format!("r#{}", result),
+            _ => result,
         }
     }
 }
@@ -314,6 +339,13 @@ mod tests {
         assert_eq!(name, "Id64");
     }
 
+    #[test]
+    fn check_def_accessor_name() {
+        let def = "inputPeerSelf = InputPeer".parse().unwrap();
+        let name = definitions::accessor_name(&def);
+        assert_eq!(name, "peer_self");
+    }
+
     // Type methods
 
     #[test]
@@ -452,6 +484,16 @@ mod tests {
         assert_eq!(name, "r#final");
     }
 
+    #[test]
+    fn check_param_keyword_attr_name() {
+        let param = "type:string".parse().unwrap();
+        assert_eq!(parameters::attr_name(&param), "r#type");
+        let param = "async:flags.1?true".parse().unwrap();
+        assert_eq!(parameters::attr_name(&param), "r#async");
+        let param = "super:int".parse().unwrap();
+        assert_eq!(parameters::attr_name(&param), "super_");
+    }
+
     #[test]
     fn check_param_self_attr_name() {
         let param = "self:flags.0?true".parse().unwrap();
@@ -11,7 +11,7 @@
 use crate::grouper;
 use crate::metadata::Metadata;
 use crate::rustifier;
-use crate::{ignore_type, Config};
+use crate::{extra_derives, ignore_type, write_deprecated, Config, SkippedDerive};
 use grammers_tl_parser::tl::{Category, Definition, ParameterType};
 use std::io::{self, Write};
 
@@ -44,10 +44,28 @@ fn get_generic_param_list(def: &Definition, trait_bounds: &str) -> String {
     result
 }
 
+/// Writes the description found in the TL comments as a doc comment:
+///
+/// ```ignore
+/// /// Description.
+/// ```
+fn write_doc_comment<W: Write>(
+    file: &mut W,
+    indent: &str,
+    description: Option<&str>,
+) -> io::Result<()> {
+    for line in description.into_iter().flat_map(str::lines) {
+        writeln!(file, "{}/// {}", indent, line)?;
+    }
+    Ok(())
+}
+
 /// Defines the `struct` corresponding to the definition:
 ///
 /// ```ignore
+/// /// Description.
 /// pub struct Name {
+///     /// Description.
 ///     pub field: Type,
 /// }
 /// ```
@@ -55,15 +73,49 @@ fn write_struct<W: Write>(
     file: &mut W,
     indent: &str,
     def: &Definition,
-    _metadata: &Metadata,
+    metadata: &Metadata,
     config: &Config,
+    skipped: &mut Vec<SkippedDerive>,
 ) -> io::Result<()> {
     // Define struct
+    if config.gen_doc_comments {
+        write_doc_comment(file, indent, def.description.as_deref())?;
+    }
+    if config.mark_deprecated {
+        write_deprecated(file, indent, def)?;
+    }
+
     if config.impl_debug {
         writeln!(file, "{}#[derive(Debug)]", indent)?;
     }
 
-    writeln!(file, "{}#[derive(Clone, PartialEq)]", indent)?;
+    if config.impl_default && metadata.is_all_optional_def(def) {
+        writeln!(file, "{}#[derive(Default)]", indent)?;
+    }
+
+    writeln!(
+        file,
+        "{}#[derive(Clone, {}PartialEq{})]",
+        indent,
+        if metadata.is_copy_def(def) {
+            "Copy, "
+        } else {
+            ""
+        },
+        extra_derives(
+            config,
+            &rustifier::definitions::qual_name(def),
+            metadata.is_eq_def(def),
+            skipped,
+        ),
+    )?;
+    if config.serde_derive {
+        writeln!(
+            file,
+            "{}#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]",
+            indent
+        )?;
+    }
     write!(
         file,
         "{}pub struct {}{} {{",
@@ -78,7 +130,23 @@ fn write_struct<W: Write>(
             ParameterType::Flags => {
                 // Flags are computed on-the-fly, not stored
             }
-            ParameterType::Normal { .. } => {
+            ParameterType::Normal { ref ty, ref flag } => {
+                if config.gen_doc_comments {
+                    write_doc_comment(
+                        file,
+                        &format!("{}    ", indent),
+                        param.description.as_deref(),
+                    )?;
+                }
+                // Flag-only booleans are not sent over the wire when unset,
+                // so there is no need to persist them either.
+                if config.serde_derive && flag.is_some() && ty.name == "true" {
+                    writeln!(
+                        file,
+                        "{}    #[cfg_attr(feature = \"serde\", serde(default, skip_serializing_if = \"std::ops::Not::not\"))]",
+                        indent
+                    )?;
+                }
                 writeln!(
                     file,
                     "{}    pub {}: {},",
@@ -123,6 +191,41 @@ fn write_identifiable<W: Write>(
     Ok(())
 }
 
+/// Defines the `impl Display` printing the name and constructor identifier of the definition:
+///
+/// ```ignore
+/// impl std::fmt::Display for Name {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("Name(0x12345678)")
+///     }
+/// }
+/// ```
+fn write_impl_display<W: Write>(file: &mut W, indent: &str, def: &Definition) -> io::Result<()> {
+    writeln!(
+        file,
+        "{}impl{} std::fmt::Display for {}{} {{",
+        indent,
+        get_generic_param_list(def, ""),
+        rustifier::definitions::type_name(def),
+        get_generic_param_list(def, ""),
+    )?;
+    writeln!(
+        file,
+        "{}    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{",
+        indent
+    )?;
+    writeln!(
+        file,
+        "{}        f.write_str(\"{}(0x{:08x})\")",
+        indent,
+        rustifier::definitions::type_name(def),
+        def.id
+    )?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
 /// Defines the `impl Serializable` corresponding to the definition:
 ///
 /// ```ignore
@@ -130,6 +233,10 @@ fn write_identifiable<W: Write>(
 ///     fn serialize(&self, buf: &mut impl Extend<u8>) {
 ///         self.field.serialize(buf);
 ///     }
+///
+///     fn serialized_len(&self) -> usize {
+///         self.field.serialized_len()
+///     }
 /// }
 /// ```
 fn write_serializable<W: Write>(
@@ -230,10 +337,52 @@ fn write_serializable<W: Write>(
     }
 
     writeln!(file, "{}    }}", indent)?;
+    write_serialized_len(file, indent, def)?;
     writeln!(file, "{}}}", indent)?;
     Ok(())
 }
 
+/// Defines the `serialized_len` method inside the `impl Serializable`:
+///
+/// ```ignore
+/// fn serialized_len(&self) -> usize {
+///     4 + self.field.serialized_len()
+/// }
+/// ```
+fn write_serialized_len<W: Write>(file: &mut W, indent: &str, def: &Definition) -> io::Result<()> {
+    let mut terms = Vec::new();
+    if def.category == Category::Functions {
+        // The `CONSTRUCTOR_ID`.
+        terms.push("4".to_string());
+    }
+    for param in def.params.iter() {
+        match &param.ty {
+            ParameterType::Flags => terms.push("4".to_string()),
+            ParameterType::Normal { ty, flag } => {
+                let name = rustifier::parameters::attr_name(param);
+                if ty.name == "true" {
+                    // Only present in the flags.
+                } else if flag.is_some() {
+                    terms.push(format!(
+                        "self.{}.as_ref().map_or(0, |x| x.serialized_len())",
+                        name
+                    ));
+                } else {
+                    terms.push(format!("self.{}.serialized_len()", name));
+                }
+            }
+        }
+    }
+    if terms.is_empty() {
+        terms.push("0".to_string());
+    }
+
+    writeln!(file, "{}    fn serialized_len(&self) -> usize {{", indent)?;
+    writeln!(file, "{}        {}", indent, terms.join(" + "))?;
+    writeln!(file, "{}    }}", indent)?;
+    Ok(())
+}
+
 /// Defines the `impl Deserializable` corresponding to the definition:
 ///
 /// ```ignore
@@ -380,7 +529,7 @@ fn write_rpc<W: Write>(
 /// }
 ///
 /// impl TryFrom<Enum> for Name {
-///     type Error = ();
+///     type Error = crate::WrongVariant;
 /// }
 /// ```
 fn write_impl_from<W: Write>(
@@ -401,7 +550,7 @@ fn write_impl_from<W: Write>(
         type_name,
     )?;
     if !infallible {
-        writeln!(file, "{}    type Error = ();", indent)?;
+        writeln!(file, "{}    type Error = crate::WrongVariant;", indent)?;
     }
     writeln!(
         file,
@@ -421,11 +570,7 @@ fn write_impl_from<W: Write>(
         name = rustifier::definitions::variant_name(def),
         data = if def.params.is_empty() { "" } else { "(x)" },
         ok = if infallible { "" } else { "Ok(" },
-        deref = if metadata.is_recursive_def(def) {
-            "*"
-        } else {
-            ""
-        },
+        deref = if metadata.is_boxed_def(def) { "*" } else { "" },
         value = if def.params.is_empty() {
             type_name.as_ref()
         } else {
@@ -435,7 +580,82 @@ fn write_impl_from<W: Write>(
         paren = if infallible { "" } else { ")" },
     )?;
     if !infallible {
-        writeln!(file, "{}            _ => Err(())", indent)?;
+        writeln!(
+            file,
+            "{}            _ => Err(crate::WrongVariant {{ id: x.constructor_id(), expected: \"{}\" }}),",
+            indent,
+            def.full_name()
+        )?;
+    }
+    writeln!(file, "{}        }}", indent)?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
+/// Defines a constructor taking only the required parameters:
+///
+/// ```ignore
+/// impl Name {
+///     pub fn new(required: Type) -> Self {
+///         Self { required, optional: None }
+///     }
+/// }
+/// ```
+fn write_constructor<W: Write>(
+    file: &mut W,
+    indent: &str,
+    def: &Definition,
+    _metadata: &Metadata,
+) -> io::Result<()> {
+    let required = def
+        .params
+        .iter()
+        .filter(|p| matches!(p.ty, ParameterType::Normal { flag: None, .. }))
+        .collect::<Vec<_>>();
+
+    writeln!(
+        file,
+        "{}impl{} {}{} {{",
+        indent,
+        get_generic_param_list(def, ""),
+        rustifier::definitions::type_name(def),
+        get_generic_param_list(def, ""),
+    )?;
+    if required.is_empty() {
+        writeln!(file, "{}    #[allow(clippy::new_without_default)]", indent)?;
+    } else if required.len() > 7 {
+        writeln!(file, "{}    #[allow(clippy::too_many_arguments)]", indent)?;
+    }
+    writeln!(
+        file,
+        "{}    pub fn new({}) -> Self {{",
+        indent,
+        required
+            .iter()
+            .map(|p| format!(
+                "{}: {}",
+                rustifier::parameters::attr_name(p),
+                rustifier::parameters::qual_name(p)
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )?;
+    writeln!(file, "{}        Self {{", indent)?;
+    for param in def.params.iter() {
+        match &param.ty {
+            ParameterType::Flags => {}
+            ParameterType::Normal { ty, flag } => {
+                let name = rustifier::parameters::attr_name(param);
+                if flag.is_none() {
+                    writeln!(file, "{}            {},", indent, name)?;
+                } else if ty.name == "true" {
+                    writeln!(file, "{}            {}: false,", indent, name)?;
+                } else {
+                    writeln!(file, "{}            {}: None,", indent, name)?;
+                }
+            }
+        }
     }
     writeln!(file, "{}        }}", indent)?;
     writeln!(file, "{}    }}", indent)?;
@@ -443,6 +663,148 @@ fn write_impl_from<W: Write>(
     Ok(())
 }
 
+/// Defines a builder for definitions with optional parameters:
+///
+/// ```ignore
+/// pub struct NameBuilder(Name);
+///
+/// impl Name {
+///     pub fn builder(required: Type) -> NameBuilder {
+///         NameBuilder(Name { required, optional: None })
+///     }
+/// }
+///
+/// impl NameBuilder {
+///     pub fn required(mut self, v: Type) -> Self {
+///         self.0.required = v;
+///         self
+///     }
+///     pub fn optional(mut self, v: Type) -> Self {
+///         self.0.optional = Some(v);
+///         self
+///     }
+///     pub fn build(self) -> Name {
+///         self.0
+///     }
+/// }
+/// ```
+fn write_builder<W: Write>(
+    file: &mut W,
+    indent: &str,
+    def: &Definition,
+    _metadata: &Metadata,
+) -> io::Result<()> {
+    let has_optional = def
+        .params
+        .iter()
+        .any(|p| matches!(p.ty, ParameterType::Normal { flag: Some(_), .. }));
+    if !has_optional {
+        return Ok(());
+    }
+
+    let type_name = rustifier::definitions::type_name(def);
+    let generics = get_generic_param_list(def, "");
+
+    writeln!(
+        file,
+        "{}pub struct {}Builder{}({}{});",
+        indent, type_name, generics, type_name, generics
+    )?;
+
+    // Required parameters are taken by `builder()`
+    writeln!(
+        file,
+        "{}impl{} {}{} {{",
+        indent, generics, type_name, generics
+    )?;
+    writeln!(file, "{}    #[allow(clippy::too_many_arguments)]", indent)?;
+    write!(file, "{}    pub fn builder(", indent)?;
+    let mut first = true;
+    for param in def.params.iter() {
+        if let ParameterType::Normal { flag: None, .. } = param.ty {
+            if !first {
+                write!(file, ", ")?;
+            }
+            first = false;
+            write!(
+                file,
+                "{}: {}",
+                rustifier::parameters::attr_name(param),
+                rustifier::parameters::qual_name(param)
+            )?;
+        }
+    }
+    writeln!(file, ") -> {}Builder{} {{", type_name, generics)?;
+    writeln!(
+        file,
+        "{}        {}Builder({} {{",
+        indent, type_name, type_name
+    )?;
+    for param in def.params.iter() {
+        match &param.ty {
+            ParameterType::Flags => {}
+            ParameterType::Normal { ty, flag } => {
+                let name = rustifier::parameters::attr_name(param);
+                if flag.is_none() {
+                    writeln!(file, "{}            {},", indent, name)?;
+                } else if ty.name == "true" {
+                    writeln!(file, "{}            {}: false,", indent, name)?;
+                } else {
+                    writeln!(file, "{}            {}: None,", indent, name)?;
+                }
+            }
+        }
+    }
+    writeln!(file, "{}        }})", indent)?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+
+    // Every parameter can be set through the builder
+    writeln!(
+        file,
+        "{}impl{} {}Builder{} {{",
+        indent, generics, type_name, generics
+    )?;
+    for param in def.params.iter() {
+        match &param.ty {
+            ParameterType::Flags => {}
+            ParameterType::Normal { ty, flag } => {
+                let name = rustifier::parameters::attr_name(param);
+                let optional = flag.is_some() && ty.name != "true";
+                writeln!(
+                    file,
+                    "{}    pub fn {}(mut self, v: {}) -> Self {{",
+                    indent,
+                    name,
+                    if optional {
+                        rustifier::types::qual_name(ty)
+                    } else {
+                        rustifier::parameters::qual_name(param)
+                    }
+                )?;
+                writeln!(
+                    file,
+                    "{}        self.0.{} = {};",
+                    indent,
+                    name,
+                    if optional { "Some(v)" } else { "v" }
+                )?;
+                writeln!(file, "{}        self", indent)?;
+                writeln!(file, "{}    }}", indent)?;
+            }
+        }
+    }
+    writeln!(
+        file,
+        "{}    pub fn build(self) -> {}{} {{",
+        indent, type_name, generics
+    )?;
+    writeln!(file, "{}        self.0", indent)?;
+    writeln!(file, "{}    }}", indent)?;
+    writeln!(file, "{}}}", indent)?;
+    Ok(())
+}
+
 /// Writes an entire definition as Rust code (`struct` and `impl`).
 fn write_definition<W: Write>(
     file: &mut W,
@@ -450,9 +812,13 @@ fn write_definition<W: Write>(
     def: &Definition,
     metadata: &Metadata,
     config: &Config,
+    skipped: &mut Vec<SkippedDerive>,
 ) -> io::Result<()> {
-    write_struct(file, indent, def, metadata, config)?;
+    write_struct(file, indent, def, metadata, config, skipped)?;
     write_identifiable(file, indent, def, metadata)?;
+    if config.impl_display {
+        write_impl_display(file, indent, def)?;
+    }
     write_serializable(file, indent, def, metadata)?;
     if def.category == Category::Types || config.deserializable_functions {
         write_deserializable(file, indent, def, metadata)?;
@@ -463,6 +829,12 @@ fn write_definition<W: Write>(
     if def.category == Category::Types && config.impl_from_enum {
         write_impl_from(file, indent, def, metadata)?;
     }
+    if config.gen_builders {
+        write_builder(file, indent, def, metadata)?;
+    }
+    if config.gen_constructors {
+        write_constructor(file, indent, def, metadata)?;
+    }
     Ok(())
 }
 
@@ -473,6 +845,7 @@ pub(crate) fn write_category_mod<W: Write>(
     definitions: &[Definition],
     metadata: &Metadata,
     config: &Config,
+    skipped: &mut Vec<SkippedDerive>,
 ) -> io::Result<()> {
     // Begin outermost mod
     match category {
@@ -488,9 +861,12 @@ pub(crate) fn write_category_mod<W: Write>(
                  /// [`Serializable`]: ../trait.Serializable.html\n\
                  /// [`Deserializable`]: ../trait.Deserializable.html\n\
                  #[allow(clippy::cognitive_complexity, clippy::identity_op, clippy::unreadable_literal)]\n\
-                 pub mod types {{\n\
                  "
             )?;
+            if config.mark_deprecated {
+                writeln!(file, "#[allow(deprecated)]")?;
+            }
+            writeln!(file, "pub mod types {{")?;
         }
         Category::Functions => {
             writeln!(
@@ -507,10 +883,13 @@ pub(crate) fn write_category_mod<W: Write>(
             /// [`Identifiable`]: ../trait.Identifiable.html\n\
             /// [`Serializable`]: ../trait.Serializable.html\n\
             /// [`RemoteCall`]: trait.RemoteCall.html\n\
-            #[allow(clippy::cognitive_complexity, clippy::identity_op, clippy::unreadable_literal)]\n\
-            pub mod functions {{
+            #[allow(clippy::cognitive_complexity, clippy::identity_op, clippy::unreadable_literal)]\
             "
             )?;
+            if config.mark_deprecated {
+                writeln!(file, "#[allow(deprecated)]")?;
+            }
+            writeln!(file, "pub mod functions {{")?;
         }
     }
 
@@ -538,7 +917,7 @@ pub(crate) fn write_category_mod<W: Write>(
             .iter()
             .filter(|def| def.category == Category::Functions || !ignore_type(&def.ty))
         {
-            write_definition(&mut file, indent, definition, metadata, config)?;
+            write_definition(&mut file, indent, definition, metadata, config, skipped)?;
         }
 
         // End possibly inner mod
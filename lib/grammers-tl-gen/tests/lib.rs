@@ -16,6 +16,20 @@ fn get_definitions(contents: &str) -> Vec<Definition> {
     parse_tl_file(contents).map(|d| d.unwrap()).collect()
 }
 
+/// Get the attributes (`#[...]` lines) right before the given item.
+fn attributes_of<'a>(result: &'a str, item: &str) -> Vec<&'a str> {
+    let end = result.find(item).expect("item not found");
+    let mut attrs = result[..end]
+        .lines()
+        .rev()
+        .skip(1)
+        .map(str::trim)
+        .take_while(|line| line.starts_with("#["))
+        .collect::<Vec<_>>();
+    attrs.reverse();
+    attrs
+}
+
 fn gen_rust_code(definitions: &[Definition]) -> io::Result<String> {
     let mut file = Vec::new();
     generate_rust_code(
@@ -26,8 +40,24 @@ fn gen_rust_code(definitions: &[Definition]) -> io::Result<String> {
             gen_name_for_id: false,
             deserializable_functions: true,
             impl_debug: true,
+            impl_display: true,
             impl_from_enum: true,
             impl_from_type: true,
+            serde_derive: true,
+            impl_default: true,
+            gen_builders: true,
+            gen_constructors: true,
+            gen_doc_comments: true,
+            gen_variant_accessors: true,
+            gen_variant_names: true,
+            gen_variant_ids: true,
+            gen_id_for_name: true,
+            non_exhaustive_enums: true,
+            extra_derives: Vec::new(),
+            impl_hash_eq: false,
+            box_variant_threshold: None,
+            mark_deprecated: true,
+            gen_prelude: true,
         },
     )?;
     Ok(String::from_utf8(file).unwrap())
@@ -119,3 +149,518 @@ fn recursive_types_vec_indirect_not_boxed() -> io::Result<()> {
     assert!(result.contains("JsonObject(crate::types::JsonObject)"));
     Ok(())
 }
+
+#[test]
+fn serde_derive_renames_variants() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerEmpty#7f3b18ea = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        ---functions---
+        messages.getDialogs#a0f4cb4f flags:# exclude_pinned:flags.0?true folder_id:flags.1?int = Bool;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains(
+        "#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]"
+    ));
+    assert!(result.contains("#[cfg_attr(feature = \"serde\", serde(rename = \"inputPeerEmpty\"))]"));
+    assert!(result.contains("#[cfg_attr(feature = \"serde\", serde(rename = \"inputPeerChat\"))]"));
+    assert!(result.contains("serde(default, skip_serializing_if = \"std::ops::Not::not\")"));
+    assert!(!result.contains("pub flags: u32"));
+    Ok(())
+}
+
+#[test]
+fn serialized_len_accounts_for_flags_and_ids() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerEmpty#7f3b18ea = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        ---functions---
+        messages.getDialogs#a0f4cb4f flags:# exclude_pinned:flags.0?true folder_id:flags.1?int offset_peer:InputPeer = Bool;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains(
+        "4 + 4 + self.folder_id.as_ref().map_or(0, |x| x.serialized_len()) + self.offset_peer.serialized_len()"
+    ));
+    assert!(result.contains("Self::Empty => 4,"));
+    assert!(result.contains("Self::Chat(x) => 4 + x.serialized_len(),"));
+    Ok(())
+}
+
+#[test]
+fn impl_default_only_all_optional() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        ---functions---
+        account.updateProfile#78515775 flags:# first_name:flags.0?string last_name:flags.1?string = User;
+        messages.getHistory#4423e6c5 peer:InputPeer offset_id:int = messages.Messages;
+        help.getConfig#c4f9186b = Config;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert_eq!(result.matches("#[derive(Default)]").count(), 1);
+    let default_pos = result.find("#[derive(Default)]").unwrap();
+    let struct_pos = result.find("pub struct UpdateProfile").unwrap();
+    assert!(default_pos < struct_pos);
+    assert!(!result[default_pos..struct_pos].contains("pub struct"));
+    Ok(())
+}
+
+#[test]
+fn builders_take_required_parameters() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        ---functions---
+        messages.sendMessage#dff8042c flags:# silent:flags.5?true peer:InputPeer message:string schedule_date:flags.10?int = Updates;
+        invokeWithLayer#da9b0d0d {X:Type} layer:int query:!X = X;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains("pub struct SendMessageBuilder(SendMessage);"));
+    assert!(result.contains(
+        "pub fn builder(peer: crate::enums::InputPeer, message: String) -> SendMessageBuilder {"
+    ));
+    assert!(result.contains("pub fn silent(mut self, v: bool) -> Self {"));
+    assert!(result.contains("pub fn schedule_date(mut self, v: i32) -> Self {"));
+    assert!(result.contains("self.0.schedule_date = Some(v);"));
+    assert!(result.contains("pub fn build(self) -> SendMessage {"));
+    assert!(!result.contains("InvokeWithLayerBuilder"));
+    Ok(())
+}
+
+#[test]
+fn copy_derived_when_all_fields_copy() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerEmpty#7f3b18ea = InputPeer;
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        inputPeerNotifySettings#cacb6ae2 flags:# silent:flags.1?Bool sound:flags.3?string = InputPeerNotifySettings;
+        inputNotifyPeer#b8bc5b0c peer:InputPeer = InputNotifyPeer;
+        textBold#6724abc4 text:RichText = RichText;
+        textConcat#7e6260d7 texts:Vector<RichText> = RichText;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    let copy = "#[derive(Clone, Copy, PartialEq)]";
+    assert!(attributes_of(&result, "pub enum InputPeer {").contains(&copy));
+    assert!(attributes_of(&result, "pub enum InputNotifyPeer {").contains(&copy));
+    assert!(attributes_of(&result, "pub struct InputPeerChat {").contains(&copy));
+    assert!(!attributes_of(&result, "pub enum InputPeerNotifySettings {").contains(&copy));
+    assert!(!attributes_of(&result, "pub enum RichText {").contains(&copy));
+    assert!(!attributes_of(&result, "pub struct TextBold {").contains(&copy));
+    Ok(())
+}
+
+#[test]
+fn constructors_take_required_parameters() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputMessageID#a676a322 id:int = InputMessage;
+        ---functions---
+        messages.sendMessage#dff8042c flags:# silent:flags.5?true peer:InputPeer message:string schedule_date:flags.10?int = Updates;
+        invokeWithLayer#da9b0d0d {X:Type} layer:int query:!X = X;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains("pub fn new(id: i32) -> Self {"));
+    assert!(result.contains("pub fn new(peer: crate::enums::InputPeer, message: String) -> Self {"));
+    assert!(result.contains("silent: false,"));
+    assert!(result.contains("schedule_date: None,"));
+    assert!(result.contains("impl<X> InvokeWithLayer<X> {"));
+    assert!(result.contains("pub fn new(layer: i32, query: X) -> Self {"));
+    Ok(())
+}
+
+#[test]
+fn doc_comments_carried_from_tl() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        // Contains the identifier of a message.
+        // @id the message identifier
+        inputMessageID#a676a322 id:int = InputMessage;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains(
+        "
+    /// Contains the identifier of a message.
+    #[derive(Debug)]"
+    ));
+    assert!(result.contains(
+        "
+        /// the message identifier
+        pub id: i32,"
+    ));
+    Ok(())
+}
+
+#[test]
+fn variant_accessors_generated() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        textEmpty#dc3d824f = RichText;
+        textBold#6724abc4 text:RichText = RichText;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains("pub fn is_peer_self(&self) -> bool {"));
+    assert!(!result.contains("pub fn as_peer_self("));
+    assert!(result.contains("pub fn as_chat(&self) -> Option<&crate::types::InputPeerChat> {"));
+    assert!(result.contains("pub fn into_chat(self) -> Option<crate::types::InputPeerChat> {"));
+    assert!(result.contains("Self::TextBold(x) => Some(x.as_ref()),"));
+    assert!(result.contains("Self::TextBold(x) => Some(*x),"));
+    Ok(())
+}
+
+#[test]
+fn enum_constructor_id_generated() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains("pub fn constructor_id(&self) -> u32 {"));
+    assert!(result.contains("Self::PeerSelf => crate::types::InputPeerSelf::CONSTRUCTOR_ID,"));
+    assert!(result.contains("Self::Chat(_) => crate::types::InputPeerChat::CONSTRUCTOR_ID,"));
+    Ok(())
+}
+
+#[test]
+fn enum_variant_names_generated() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        storage.fileUnknown#aa963b05 = storage.FileType;
+        phone.groupParticipants#f47751b6 count:int name:string = phone.GroupParticipants;
+        phone.groupParticipantsEmpty#f47751b7 name:string = phone.GroupParticipants;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains("Self::PeerSelf => \"inputPeerSelf\","));
+    assert!(result.contains("Self::FileUnknown => \"storage.fileUnknown\","));
+    // Would collide with the method to access the common `name` field.
+    assert!(!result.contains("Self::GroupParticipants(_) => \"phone.groupParticipants\","));
+    Ok(())
+}
+
+#[test]
+fn enum_variant_ids_follow_deserialize_order() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    let ids = &result[result
+        .find("pub const VARIANT_IDS: &'static [u32] = {")
+        .unwrap()..];
+    let ids = &ids[..ids.find("};").unwrap()];
+    let self_id = ids
+        .find("crate::types::InputPeerSelf::CONSTRUCTOR_ID,")
+        .unwrap();
+    let chat_id = ids
+        .find("crate::types::InputPeerChat::CONSTRUCTOR_ID,")
+        .unwrap();
+    assert!(self_id < chat_id);
+
+    let self_arm = result
+        .find("crate::types::InputPeerSelf::CONSTRUCTOR_ID => ")
+        .unwrap();
+    let chat_arm = result
+        .find("crate::types::InputPeerChat::CONSTRUCTOR_ID => ")
+        .unwrap();
+    assert!(self_arm < chat_arm);
+    Ok(())
+}
+
+#[test]
+fn keyword_parameters_are_escaped() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputKeyboard#a1b2c3d4 flags:# type:string async:flags.0?true where:flags.1?int = InputKeyboard;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains("pub r#type: String,"));
+    assert!(result.contains("pub r#async: bool,"));
+    assert!(result.contains("pub r#where: Option<i32>,"));
+    assert!(result.contains("self.r#type.serialize(buf);"));
+    assert!(result.contains("let r#type = String::deserialize(buf)?;"));
+    assert!(!result.contains(" type:"));
+    Ok(())
+}
+
+#[test]
+fn deprecated_definitions_are_marked() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        // @deprecated use inputPeerChat instead
+        inputPeerOldChat#1a2b3c4d chat_id:int = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        ---functions---
+        //@deprecated
+        messages.oldSendMessage#5a6b7c8d message:string = InputPeer;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert_eq!(
+        attributes_of(&result, "pub struct InputPeerOldChat {")[0],
+        "#[deprecated(note = \"use inputPeerChat instead\")]"
+    );
+    assert_eq!(
+        attributes_of(&result, "pub struct OldSendMessage {")[0],
+        "#[deprecated]"
+    );
+    assert!(!attributes_of(&result, "pub struct InputPeerChat {").contains(&"#[deprecated]"));
+    assert!(
+        result.contains("#[deprecated(note = \"use inputPeerChat instead\")]\n        OldChat(")
+    );
+    // Deprecated variants are still deserialized, without warnings in the generated code.
+    assert!(result.contains("crate::types::InputPeerOldChat::CONSTRUCTOR_ID => Self::OldChat("));
+    assert!(result.contains("#[allow(deprecated)]\npub mod enums {"));
+    Ok(())
+}
+
+#[test]
+fn unexpected_constructor_names_expected_type() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        storage.fileUnknown#aa963b05 = storage.FileType;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    eprintln!("{}", result);
+    assert!(result.contains(
+        "crate::deserialize::Error::UnexpectedConstructor { id, expected: \"storage.FileType\" }"
+    ));
+    Ok(())
+}
+
+#[test]
+fn duplicate_constructor_ids_are_rejected() {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerEmpty#7da07ec9 = InputPeer;
+    ",
+    );
+    let err = gen_rust_code(&definitions).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("inputPeerSelf and inputPeerEmpty"));
+}
+
+#[test]
+fn enums_are_non_exhaustive() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerEmpty#7f3b18ea = InputPeer;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    assert!(result.contains("#[non_exhaustive]\n    pub enum InputPeer {"));
+    Ok(())
+}
+
+#[test]
+fn extra_derives_skip_types_with_floats() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerChannel#27bcbbfc channel_id:long access_hash:long = InputPeer;
+        geoPoint#b2a2f663 long:double lat:double access_hash:long = GeoPoint;
+        inputMediaGeoPoint#f9c44144 geo_point:GeoPoint = InputMedia;
+    ",
+    );
+    let mut file = Vec::new();
+    let skipped = generate_rust_code(
+        &mut file,
+        &definitions,
+        LAYER,
+        &Config {
+            extra_derives: vec!["Eq".into(), "Hash".into()],
+            ..Default::default()
+        },
+    )?;
+    let result = String::from_utf8(file).unwrap();
+    assert!(result.contains(
+        "#[derive(Clone, Copy, PartialEq, Eq, Hash)]\n    pub struct InputPeerChannel {"
+    ));
+    assert!(result.contains("#[derive(Clone, Copy, PartialEq)]\n    pub struct GeoPoint {"));
+    assert!(result.contains("#[derive(Clone, Copy, PartialEq)]\n    pub enum InputMedia {"));
+
+    let skipped = skipped
+        .iter()
+        .map(|s| (s.derive.as_str(), s.type_name.as_str()))
+        .collect::<Vec<_>>();
+    assert!(skipped.contains(&("Eq", "crate::types::GeoPoint")));
+    assert!(skipped.contains(&("Hash", "crate::enums::InputMedia")));
+    assert!(!skipped.iter().any(|&(_, name)| name.ends_with("InputPeerChannel")));
+    Ok(())
+}
+
+#[test]
+fn hash_eq_only_for_types_without_floats() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerChannel#27bcbbfc channel_id:long access_hash:long = InputPeer;
+        jsonNull#3f6d7b68 = JSONValue;
+        jsonNumber#2be0dfa4 value:double = JSONValue;
+        jsonArray#f7444763 value:Vector<JSONValue> = JSONValue;
+    ",
+    );
+    let mut file = Vec::new();
+    generate_rust_code(
+        &mut file,
+        &definitions,
+        LAYER,
+        &Config {
+            impl_hash_eq: true,
+            extra_derives: vec!["Hash".into()],
+            ..Default::default()
+        },
+    )?;
+    let result = String::from_utf8(file).unwrap();
+    let hash_eq = "#[derive(Clone, Copy, PartialEq, Eq, Hash)]";
+    assert!(attributes_of(&result, "pub struct InputPeerChannel {").contains(&hash_eq));
+    assert!(attributes_of(&result, "pub enum InputPeer {").contains(&hash_eq));
+    assert!(attributes_of(&result, "pub struct JsonNull {").contains(&hash_eq));
+    assert!(attributes_of(&result, "pub struct JsonNumber {")
+        .contains(&"#[derive(Clone, Copy, PartialEq)]"));
+    let plain = "#[derive(Clone, PartialEq)]";
+    assert!(attributes_of(&result, "pub struct JsonArray {").contains(&plain));
+    assert!(attributes_of(&result, "pub enum Jsonvalue {").contains(&plain));
+    Ok(())
+}
+
+#[test]
+fn large_variants_boxed_over_threshold() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        inputPeerChannel#27bcbbfc channel_id:long access_hash:long title:string about:string = InputPeer;
+    ",
+    );
+    let mut file = Vec::new();
+    generate_rust_code(
+        &mut file,
+        &definitions,
+        LAYER,
+        &Config {
+            box_variant_threshold: Some(16),
+            ..Default::default()
+        },
+    )?;
+    let result = String::from_utf8(file).unwrap();
+    eprintln!("{}", result);
+    assert!(result.contains("Chat(crate::types::InputPeerChat),"));
+    assert!(result.contains("Channel(Box<crate::types::InputPeerChannel>),"));
+    assert!(
+        result.contains("Channel(Box::new(crate::types::InputPeerChannel::deserialize(buf)?)),")
+    );
+    assert!(result.contains("InputPeer::Channel(Box::new(x))"));
+    assert!(result.contains("crate::enums::InputPeer::Channel(x) => Ok(*x),"));
+    assert!(!attributes_of(&result, "pub enum InputPeer {")
+        .contains(&"#[derive(Clone, Copy, PartialEq)]"));
+    Ok(())
+}
+
+#[test]
+fn try_from_enum_reports_wrong_variant() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    assert!(result.contains("type Error = crate::WrongVariant;"));
+    assert!(result.contains(
+        "_ => Err(crate::WrongVariant { id: x.constructor_id(), expected: \"inputPeerChat\" }),"
+    ));
+    Ok(())
+}
+
+#[test]
+fn prelude_reexports_without_clashes() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputUserSelf#f7c1b13f = InputUser;
+        inputUser#f21158c6 user_id:long access_hash:long = InputUser;
+        photo#fb197a65 id:long = Photo;
+        photos.photo#20212ca8 photo:Photo = photos.Photo;
+    ",
+    );
+    let mut file = Vec::new();
+    generate_rust_code(
+        &mut file,
+        &definitions,
+        LAYER,
+        &Config {
+            gen_prelude: true,
+            ..Default::default()
+        },
+    )?;
+    let result = String::from_utf8(file).unwrap();
+    let prelude = &result[result.find("pub mod prelude {").expect("no prelude")..];
+    assert!(prelude.contains("pub use crate::enums::InputPeer;"));
+    assert!(prelude.contains("pub use crate::enums::InputUser;"));
+    assert!(prelude.contains("pub use crate::enums::Photo;"));
+    assert!(prelude.contains("pub use crate::types::InputPeerSelf;"));
+    assert!(prelude.contains("pub use crate::types::InputUserSelf;"));
+    assert!(!prelude.contains("pub use crate::types::InputUser;"));
+    assert!(!prelude.contains("photos"));
+    Ok(())
+}
+
+#[test]
+fn prelude_not_generated_by_default() -> io::Result<()> {
+    let definitions = get_definitions("inputPeerSelf#7da07ec9 = InputPeer;");
+    let mut file = Vec::new();
+    generate_rust_code(&mut file, &definitions, LAYER, &Config::default())?;
+    assert!(!String::from_utf8(file).unwrap().contains("pub mod prelude"));
+    Ok(())
+}
+
+#[test]
+fn display_prints_name_and_id() -> io::Result<()> {
+    let definitions = get_definitions(
+        "
+        inputPeerSelf#7da07ec9 = InputPeer;
+        inputPeerChat#35a95cb9 chat_id:long = InputPeer;
+        ---functions---
+        invokeWithLayer#da9b0d0d {X:Type} layer:int query:!X = X;
+    ",
+    );
+    let result = gen_rust_code(&definitions)?;
+    assert!(result.contains("impl std::fmt::Display for InputPeer {"));
+    assert!(result.contains("Self::PeerSelf => f.write_str(\"InputPeerSelf(0x7da07ec9)\"),"));
+    assert!(result.contains("Self::Chat(_) => f.write_str(\"InputPeerChat(0x35a95cb9)\"),"));
+    assert!(result.contains("impl std::fmt::Display for InputPeerChat {"));
+    assert!(result.contains("impl<X> std::fmt::Display for InvokeWithLayer<X> {"));
+    assert!(result.contains("f.write_str(\"InvokeWithLayer(0xda9b0d0d)\")"));
+    Ok(())
+}
@@ -16,6 +16,7 @@ use crate::utils::infer_id;
 ///
 /// [Type Language]: https://core.telegram.org/mtproto/TL
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub struct Definition {
     /// The namespace components of the definition. This list will be empty
     /// if the name of the definition belongs to the global namespace.
@@ -38,6 +39,13 @@ pub struct Definition {
 
     /// The category to which this definition belongs to.
     pub category: Category,
+
+    /// The description found in the comments preceding this definition, if any.
+    pub description: Option<String>,
+
+    /// The note of the `@deprecated` tag found in the comments preceding this definition,
+    /// if any. The note is empty if the tag had no text.
+    pub deprecated: Option<String>,
 }
 
 impl fmt::Display for Definition {
@@ -151,6 +159,7 @@ impl FromStr for Definition {
                 Ok(Parameter {
                     ref name,
                     ty: ParameterType::Flags,
+                    ..
                 }) => {
                     flag_defs.push(name.clone());
                     Some(Ok(p.unwrap()))
@@ -217,6 +226,8 @@ impl FromStr for Definition {
             params,
             ty,
             category: Category::Types,
+            description: None,
+            deprecated: None,
         })
     }
 }
@@ -405,6 +416,7 @@ mod tests {
                     Parameter {
                         name: "flags".into(),
                         ty: ParameterType::Flags,
+                        description: None,
                     },
                     Parameter {
                         name: "pname".into(),
@@ -427,6 +439,7 @@ mod tests {
                                 index: 10
                             })
                         },
+                        description: None,
                     },
                 ],
                 ty: Type {
@@ -437,6 +450,8 @@ mod tests {
                     generic_arg: None,
                 },
                 category: Category::Types,
+                description: None,
+                deprecated: None,
             })
         );
     }
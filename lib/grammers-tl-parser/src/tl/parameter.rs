@@ -13,12 +13,17 @@ use crate::tl::ParameterType;
 
 /// A single parameter, with a name and a type.
 #[derive(Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub struct Parameter {
     /// The name of the parameter.
     pub name: String,
 
     /// The type of the parameter.
     pub ty: ParameterType,
+
+    /// The description of the parameter found in the comments preceding
+    /// its definition, if any.
+    pub description: Option<String>,
 }
 
 impl fmt::Display for Parameter {
@@ -73,6 +78,7 @@ impl FromStr for Parameter {
         Ok(Parameter {
             name: name.into(),
             ty: ty.parse()?,
+            description: None,
         })
     }
 }
@@ -159,7 +165,8 @@ mod tests {
             Parameter::from_str("foo:#"),
             Ok(Parameter {
                 name: "foo".into(),
-                ty: ParameterType::Flags
+                ty: ParameterType::Flags,
+                description: None,
             })
         );
         assert_eq!(
@@ -175,7 +182,8 @@ mod tests {
                         generic_arg: None,
                     },
                     flag: None,
-                }
+                },
+                description: None,
             })
         );
         assert_eq!(
@@ -194,7 +202,8 @@ mod tests {
                         name: "bar".into(),
                         index: 1,
                     }),
-                }
+                },
+                description: None,
             })
         );
         assert_eq!(
@@ -210,7 +219,8 @@ mod tests {
                         generic_arg: Some(Box::new("baz".parse().unwrap())),
                     },
                     flag: None,
-                }
+                },
+                description: None,
             })
         );
         assert_eq!(
@@ -229,7 +239,8 @@ mod tests {
                         name: "bar".into(),
                         index: 1,
                     }),
-                }
+                },
+                description: None,
             })
         );
     }
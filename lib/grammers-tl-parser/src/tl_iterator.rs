@@ -18,18 +18,107 @@ const TYPES_SEP: &str = "---types---";
 /// [Type Language]: https://core.telegram.org/mtproto/TL
 pub struct TlIterator {
     contents: String,
+    comments: Vec<(usize, String)>,
+    comment_index: usize,
     index: usize,
     category: Category,
 }
 
 impl TlIterator {
     pub(crate) fn new(contents: &str) -> Self {
+        let (contents, comments) = remove_tl_comments(contents);
         TlIterator {
-            contents: remove_tl_comments(contents),
+            contents,
+            comments,
+            comment_index: 0,
             index: 0,
             category: Category::Types,
         }
     }
+
+    /// Takes the comments found before the given offset which have not been taken yet.
+    fn take_comments(&mut self, end: usize) -> &[(usize, String)] {
+        let start = self.comment_index;
+        while self
+            .comments
+            .get(self.comment_index)
+            .is_some_and(|(offset, _)| *offset < end)
+        {
+            self.comment_index += 1;
+        }
+        &self.comments[start..self.comment_index]
+    }
+}
+
+/// Fills in the descriptions of the definition and its parameters from its comments.
+///
+/// Text preceded by `@name` describes the parameter with that name, while any other
+/// text (or text preceded by `@description`) describes the definition itself. Text
+/// preceded by `@deprecated` marks the definition as deprecated, and explains why.
+fn describe(def: &mut Definition, comments: &[(usize, String)]) {
+    fn append(description: &mut Option<String>, text: &str) {
+        match description {
+            Some(description) => {
+                description.push('\n');
+                description.push_str(text);
+            }
+            None => *description = Some(text.to_string()),
+        }
+    }
+
+    enum Target<'a> {
+        Description,
+        Deprecated,
+        Param(&'a str),
+    }
+
+    let mut target = Target::Description;
+    for (_, comment) in comments {
+        let mut rest = comment.as_str();
+        loop {
+            let tag = rest
+                .char_indices()
+                .find(|&(i, c)| c == '@' && (i == 0 || rest[..i].ends_with(char::is_whitespace)));
+            let text = match tag {
+                Some((i, _)) => &rest[..i],
+                None => rest,
+            }
+            .trim();
+
+            if !text.is_empty() {
+                match target {
+                    Target::Description => append(&mut def.description, text),
+                    Target::Deprecated => match &mut def.deprecated {
+                        Some(note) if note.is_empty() => note.push_str(text),
+                        note => append(note, text),
+                    },
+                    Target::Param(name) => {
+                        if let Some(param) = def.params.iter_mut().find(|p| p.name == name) {
+                            append(&mut param.description, text);
+                        }
+                    }
+                }
+            }
+
+            match tag {
+                Some((i, _)) => {
+                    let (name, text) = rest[i + 1..]
+                        .split_once(char::is_whitespace)
+                        .unwrap_or((&rest[i + 1..], ""));
+                    target = match name {
+                        "description" => Target::Description,
+                        "deprecated" => {
+                            def.deprecated.get_or_insert_with(String::new);
+                            Target::Deprecated
+                        }
+                        name => Target::Param(name),
+                    };
+                    rest = text;
+                }
+                None => break,
+            }
+        }
+    }
 }
 
 impl Iterator for TlIterator {
@@ -46,13 +135,15 @@ impl Iterator for TlIterator {
                 self.contents.len()
             };
 
-            let definition = self.contents[self.index..end].trim();
+            let start = self.index;
             self.index = end + DEFINITION_SEP.len();
 
-            if !definition.is_empty() {
-                break definition;
+            if !self.contents[start..end].trim().is_empty() {
+                break start..end;
             }
         };
+        let comments = self.take_comments(definition.end).to_vec();
+        let definition = self.contents[definition].trim();
 
         // Get rid of the leading separator and adjust category
         let definition = if definition.starts_with("---") {
@@ -73,6 +164,7 @@ impl Iterator for TlIterator {
         Some(match definition.parse::<Definition>() {
             Ok(mut d) => {
                 d.category = self.category;
+                describe(&mut d, &comments);
                 Ok(d)
             }
             x => x,
@@ -109,4 +201,61 @@ mod tests {
         assert_eq!(it.next().unwrap().unwrap().id, 3);
         assert_eq!(it.next(), None);
     }
+
+    #[test]
+    fn parse_descriptions() {
+        let mut it = TlIterator::new(
+            "
+            // detached comment
+
+            // Some definition.
+            // @a the first @b the second
+            first#1 a:int b:int = t; // inline comment
+            second#2 = t;
+            //@description Another definition.
+            //@c
+            //@d unknown
+            ---functions---
+            third#3 c:int = t;
+        ",
+        );
+
+        let def = it.next().unwrap().unwrap();
+        assert_eq!(def.description.as_deref(), Some("Some definition."));
+        assert_eq!(def.params[0].description.as_deref(), Some("the first"));
+        assert_eq!(def.params[1].description.as_deref(), Some("the second"));
+
+        let def = it.next().unwrap().unwrap();
+        assert_eq!(def.description, None);
+
+        let def = it.next().unwrap().unwrap();
+        assert_eq!(def.description.as_deref(), Some("Another definition."));
+        assert_eq!(def.params[0].description, None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn parse_deprecated() {
+        let mut it = TlIterator::new(
+            "
+            // Some definition.
+            // @deprecated use second instead
+            first#1 = t;
+            //@deprecated
+            second#2 = t;
+            third#3 = t;
+        ",
+        );
+
+        let def = it.next().unwrap().unwrap();
+        assert_eq!(def.description.as_deref(), Some("Some definition."));
+        assert_eq!(def.deprecated.as_deref(), Some("use second instead"));
+
+        let def = it.next().unwrap().unwrap();
+        assert_eq!(def.deprecated.as_deref(), Some(""));
+
+        let def = it.next().unwrap().unwrap();
+        assert_eq!(def.deprecated, None);
+        assert_eq!(it.next(), None);
+    }
 }
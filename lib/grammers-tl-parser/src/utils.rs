@@ -11,24 +11,41 @@
 use crc32fast::Hasher;
 
 /// Removes all single-line comments from the contents.
-pub(crate) fn remove_tl_comments(contents: &str) -> String {
+///
+/// Comments which take up an entire line are also returned, along with the
+/// offset in the resulting string where they were found. Comments followed
+/// by an empty line are considered detached and are not returned.
+pub(crate) fn remove_tl_comments(contents: &str) -> (String, Vec<(usize, String)>) {
     let mut result = String::with_capacity(contents.len());
-    let mut in_comment = false;
-
-    contents.chars().enumerate().for_each(|(i, c)| {
-        if contents[i..contents.len().min(i + 2)] == *"//" {
-            in_comment = true;
-        } else if in_comment && c == '\n' {
-            in_comment = false;
-        }
-
-        if !in_comment {
-            result.push(c);
+    let mut comments = Vec::new();
+    let mut attached = 0;
+
+    for line in contents.split_inclusive('\n') {
+        let (code, newline) = match line.strip_suffix('\n') {
+            Some(code) => (code, "\n"),
+            None => (line, ""),
+        };
+
+        if let Some(pos) = code.find("//") {
+            result.push_str(&code[..pos]);
+            if code[..pos].trim().is_empty() {
+                let comment = code[pos..].trim_start_matches('/').trim();
+                comments.push((result.len(), comment.to_string()));
+            } else {
+                attached = comments.len();
+            }
+        } else if code.trim().is_empty() {
+            comments.truncate(attached);
+            result.push_str(code);
+        } else {
+            attached = comments.len();
+            result.push_str(code);
         }
-    });
+        result.push_str(newline);
+    }
 
     result.shrink_to_fit();
-    result
+    (result, comments)
 }
 
 /// Infers the identifier for a definition.
@@ -60,31 +77,42 @@ mod tests {
     #[test]
     fn remove_comments_noop() {
         let data = "hello\nworld";
-        assert_eq!(remove_tl_comments(data), data);
+        assert_eq!(remove_tl_comments(data).0, data);
 
         let data = " \nhello\nworld\n ";
-        assert_eq!(remove_tl_comments(data), data);
+        assert_eq!(remove_tl_comments(data).0, data);
     }
 
     #[test]
     fn remove_comments_leading() {
         let input = " // hello\n world ";
         let expected = " \n world ";
-        assert_eq!(remove_tl_comments(input), expected);
+        assert_eq!(remove_tl_comments(input).0, expected);
     }
 
     #[test]
     fn remove_comments_trailing() {
         let input = " \nhello \n // world \n \n ";
         let expected = " \nhello \n \n \n ";
-        assert_eq!(remove_tl_comments(input), expected);
+        assert_eq!(remove_tl_comments(input).0, expected);
     }
 
     #[test]
     fn remove_comments_many() {
         let input = "no\n//yes\nno\n//yes\nno\n";
         let expected = "no\n\nno\n\nno\n";
-        assert_eq!(remove_tl_comments(input), expected);
+        assert_eq!(remove_tl_comments(input).0, expected);
+    }
+
+    #[test]
+    fn remove_comments_returns_attached() {
+        let input = "//detached\n\n// first\n/// second\nno // inline\n";
+        let (result, comments) = remove_tl_comments(input);
+        assert_eq!(result, "\n\n\n\nno \n");
+        assert_eq!(
+            comments,
+            vec![(2, "first".to_string()), (3, "second".to_string())]
+        );
     }
 
     #[test]
@@ -74,8 +74,26 @@ fn main() -> std::io::Result<()> {
         gen_name_for_id: true,
         deserializable_functions: cfg!(feature = "deserializable-functions"),
         impl_debug: cfg!(feature = "impl-debug"),
+        impl_display: cfg!(feature = "impl-display"),
         impl_from_enum: cfg!(feature = "impl-from-enum"),
         impl_from_type: cfg!(feature = "impl-from-type"),
+        serde_derive: cfg!(feature = "serde"),
+        impl_default: cfg!(feature = "impl-default"),
+        gen_builders: cfg!(feature = "builders"),
+        gen_constructors: cfg!(feature = "constructors"),
+        gen_doc_comments: cfg!(feature = "doc-comments"),
+        gen_variant_accessors: cfg!(feature = "variant-accessors"),
+        gen_variant_names: cfg!(feature = "variant-names"),
+        gen_variant_ids: cfg!(feature = "variant-ids"),
+        gen_id_for_name: cfg!(feature = "id-for-name"),
+        // The rest of the workspace matches on these enums exhaustively.
+        non_exhaustive_enums: false,
+        extra_derives: Vec::new(),
+        impl_hash_eq: cfg!(feature = "impl-hash-eq"),
+        // Boxing more variants would break code matching on their contents directly.
+        box_variant_threshold: None,
+        mark_deprecated: cfg!(feature = "mark-deprecated"),
+        gen_prelude: cfg!(feature = "prelude"),
     };
 
     generate_rust_code(&mut file, &definitions, layer, &config)?;
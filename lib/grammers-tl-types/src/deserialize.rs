@@ -29,6 +29,9 @@ pub enum Error {
     UnexpectedConstructor {
         /// The unexpected constructor identifier.
         id: u32,
+
+        /// The name of the type that was expected instead.
+        expected: &'static str,
     },
 }
 
@@ -38,7 +41,11 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::UnexpectedEof => write!(f, "unexpected eof"),
-            Self::UnexpectedConstructor { id } => write!(f, "unexpected constructor: {:08x}", id),
+            Self::UnexpectedConstructor { id, expected } => write!(
+                f,
+                "unexpected constructor {:08x} while deserializing {}",
+                id, expected
+            ),
         }
     }
 }
@@ -144,7 +151,10 @@ impl Deserializable for bool {
         match id {
             0x997275b5u32 => Ok(true),
             0xbc799737u32 => Ok(false),
-            _ => Err(Error::UnexpectedConstructor { id }),
+            _ => Err(Error::UnexpectedConstructor {
+                id,
+                expected: "Bool",
+            }),
         }
     }
 }
@@ -309,7 +319,10 @@ impl<T: Deserializable> Deserializable for Vec<T> {
     fn deserialize(buf: Buffer) -> Result<Self> {
         let id = u32::deserialize(buf)?;
         if id != 0x1cb5c415u32 {
-            return Err(Error::UnexpectedConstructor { id });
+            return Err(Error::UnexpectedConstructor {
+                id,
+                expected: "Vector",
+            });
         }
         let len = u32::deserialize(buf)?;
         (0..len).map(|_| T::deserialize(buf)).collect()
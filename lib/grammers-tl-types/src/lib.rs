@@ -50,49 +50,164 @@
 //!
 //! The available features are:
 //!
+//! * `builders`: generates a builder for the definitions with optional
+//!   fields, so that only the required fields need to be given up-front.
+//!
+//! * `constructors`: generates a `new` function for all definitions, which
+//!   takes only the required fields and leaves all optional fields unset:
+//!
+//!   ```
+//!   # #[cfg(all(feature = "tl-api", feature = "constructors"))]
+//!   # {
+//!   use grammers_tl_types::types;
+//!
+//!   let id = types::InputMessageId::new(42);
+//!   assert_eq!(id.id, 42);
+//!   # }
+//!   ```
+//!
 //! * `deserializable-functions`: implements [`Deserializable`] for
 //!   [`functions`]. This might be of interest for server implementations,
 //!   which need to deserialize the client's requests, but is otherwise not
 //!   required.
 //!
+//! * `doc-comments`: carries the comments found in the `.tl` files over to
+//!   the generated code as documentation.
+//!
+//! * `id-for-name`: generates the [`id_for_name`] function, the reverse of
+//!   [`name_for_id`].
+//!
 //! * `impl-debug`: implements `Debug` for the generated code.
 //!
+//! * `impl-display`: implements `Display` for the generated code, printing
+//!   the name of the definition (of the variant, for enums) along with its
+//!   constructor identifier. This is much shorter than the `Debug` output:
+//!
+//!   ```
+//!   # #[cfg(all(feature = "tl-api", feature = "impl-display"))]
+//!   # {
+//!   use grammers_tl_types::enums;
+//!
+//!   let peer = enums::InputPeer::PeerSelf;
+//!   assert_eq!(peer.to_string(), "InputPeerSelf(0x7da07ec9)");
+//!   # }
+//!   ```
+//!
+//! * `impl-default`: implements `Default` for the generated code in which
+//!   every field is optional (that is, all of them depend on a flag).
+//!
 //! * `impl-from-enum`: implements `TryFrom<Enum> for Type`.
 //!
 //! * `impl-from-type`: implements `From<Type> for Enum`.
 //!
+//! * `impl-hash-eq`: implements `Eq` and `Hash` for the generated code in
+//!   which no field (however deeply nested) is a floating point number.
+//!
+//! * `mark-deprecated`: marks the generated code for definitions tagged as
+//!   `@deprecated` in the `.tl` files with `#[deprecated]`.
+//!
+//! * `prelude`: generates a [`prelude`] module, re-exporting the boxed types
+//!   without a namespace and the constructors needed to refer to peers, so
+//!   that they can be glob-imported:
+//!
+//!   ```
+//!   # #[cfg(all(feature = "tl-api", feature = "prelude"))]
+//!   # {
+//!   use grammers_tl_types::prelude::*;
+//!
+//!   let peer: InputPeer = InputPeerChat { chat_id: 42 }.into();
+//!   # }
+//!   ```
+//!
+//! * `serde`: implements `serde::Serialize` and `serde::Deserialize` for
+//!   the generated code. Boxed types use the name of the TL constructor as
+//!   the name of each variant.
+//!
 //! * `tl-api`: generates code for the `api.tl`.
 //!   This is what high-level libraries often need.
 //!
 //! * `tl-mtproto`: generates code for the `mtproto.tl`.
 //!   Only useful for low-level libraries.
 //!
+//! * `variant-accessors`: generates `is_*`, `as_*` and `into_*` methods on
+//!   the [`enums`] to check for, borrow or take out a particular variant:
+//!
+//!   ```
+//!   # #[cfg(all(feature = "tl-api", feature = "variant-accessors"))]
+//!   # {
+//!   use grammers_tl_types::enums;
+//!
+//!   let peer = enums::InputPeer::PeerSelf;
+//!   assert!(peer.is_peer_self());
+//!   assert!(peer.as_user().is_none());
+//!   # }
+//!   ```
+//!
+//! * `variant-names`: generates a `name` method on the [`enums`] returning
+//!   the name of the TL definition of the variant, the same as [`name_for_id`]
+//!   would return for its constructor identifier:
+//!
+//!   ```
+//!   # #[cfg(all(feature = "tl-api", feature = "variant-names"))]
+//!   # {
+//!   use grammers_tl_types::{enums, name_for_id};
+//!
+//!   let peer = enums::InputPeer::PeerSelf;
+//!   assert_eq!(peer.name(), "inputPeerSelf");
+//!   assert_eq!(peer.name(), name_for_id(peer.constructor_id()));
+//!   # }
+//!   ```
+//!
+//! * `variant-ids`: generates a `VARIANT_IDS` constant on the [`enums`] listing the
+//!   constructor identifiers of all of their variants, in the order they are
+//!   checked when deserializing:
+//!
+//!   ```
+//!   # #[cfg(all(feature = "tl-api", feature = "variant-ids"))]
+//!   # {
+//!   use grammers_tl_types::{enums, types, Identifiable};
+//!
+//!   assert!(enums::InputPeer::VARIANT_IDS.contains(&types::InputPeerSelf::CONSTRUCTOR_ID));
+//!   # }
+//!   ```
+//!
 //! [`types`]: types/index.html
+//! [`enums`]: enums/index.html
 //! [`functions`]: functions/index.html
+//! [`prelude`]: prelude/index.html
 //! [`RemoteCall`]: trait.RemoteCall.html
 //! [`Serializable`]: trait.Serializable.html
 //! [`Deserializable`]: trait.Deserializable.html
 //! [`LAYER`]: constant.LAYER.html
+//! [`name_for_id`]: fn.name_for_id.html
+//! [`id_for_name`]: fn.id_for_name.html
 pub mod deserialize;
 mod generated;
 pub mod serialize;
 
 pub use deserialize::{Cursor, Deserializable};
 pub use generated::{enums, functions, name_for_id, types, LAYER};
+#[cfg(feature = "id-for-name")]
+pub use generated::id_for_name;
+#[cfg(feature = "prelude")]
+pub use generated::prelude;
 pub use serialize::Serializable;
+use std::fmt;
 
 /// This struct represents the concrete type of a vector, that is,
 /// `vector` as opposed to the type `Vector`. This bare type is less
 /// common, so instead of creating a enum for `Vector` wrapping `vector`
 /// as Rust's `Vec` (as we would do with auto-generated code),
 /// a new-type for `vector` is used instead.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RawVec<T>(pub Vec<T>);
 
 /// This struct represents an unparsed blob, which should not be deserialized
 /// as a bytes string. Used by functions returning generic objects which pass
 /// the underlying result without any modification or interpretation.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Blob(pub Vec<u8>);
 
 impl From<Vec<u8>> for Blob {
@@ -101,6 +216,42 @@ impl From<Vec<u8>> for Blob {
     }
 }
 
+/// The error returned when converting a boxed type from the [`enums`] into one of the
+/// [`types`] fails, because the value is a different variant.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "tl-api", feature = "impl-from-enum"))]
+/// # {
+/// use grammers_tl_types::{enums, types};
+///
+/// let err = types::InputPeerChannel::try_from(enums::InputPeer::PeerSelf).unwrap_err();
+/// assert_eq!(err.expected, "inputPeerChannel");
+/// assert_eq!(err.id, enums::InputPeer::PeerSelf.constructor_id());
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrongVariant {
+    /// The constructor identifier of the variant that was found.
+    pub id: u32,
+
+    /// The name of the constructor that was expected instead.
+    pub expected: &'static str,
+}
+
+impl std::error::Error for WrongVariant {}
+
+impl fmt::Display for WrongVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "found constructor {:08x} while converting into {}",
+            self.id, self.expected
+        )
+    }
+}
+
 /// Anything implementing this trait is identifiable by both ends (client-server)
 /// when performing Remote Procedure Calls (RPC) and transmission of objects.
 pub trait Identifiable {
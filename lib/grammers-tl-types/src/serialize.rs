@@ -5,6 +5,7 @@
 // <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
+use std::io::{self, Write};
 
 /// This trait allows for concrete instances to be serialized into
 /// binary data as specified by the [Binary Data Serialization].
@@ -22,6 +23,119 @@ pub trait Serializable {
         self.serialize(&mut buffer);
         buffer
     }
+
+    /// The exact amount of bytes [`Serializable::serialize`] will produce,
+    /// which can be used to pre-allocate the buffer before serializing.
+    ///
+    /// The default implementation counts the bytes without storing them,
+    /// but all of the generated types compute it directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grammers_tl_types::{enums, functions, Serializable};
+    ///
+    /// let request = functions::messages::SendMessage {
+    ///     no_webpage: false,
+    ///     silent: true,
+    ///     background: false,
+    ///     clear_draft: false,
+    ///     noforwards: false,
+    ///     update_stickersets_order: false,
+    ///     invert_media: false,
+    ///     peer: enums::InputPeer::PeerSelf,
+    ///     reply_to: None,
+    ///     message: "Hello, world!".to_string(),
+    ///     random_id: 1234,
+    ///     reply_markup: None,
+    ///     entities: Some(Vec::new()),
+    ///     schedule_date: None,
+    ///     send_as: None,
+    ///     quick_reply_shortcut: None,
+    /// };
+    ///
+    /// let mut buffer = Vec::with_capacity(request.serialized_len());
+    /// request.serialize(&mut buffer);
+    /// assert_eq!(buffer.len(), request.serialized_len());
+    /// ```
+    fn serialized_len(&self) -> usize {
+        struct Counter(usize);
+
+        impl Extend<u8> for Counter {
+            fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+                self.0 += iter.into_iter().count();
+            }
+        }
+
+        let mut counter = Counter(0);
+        self.serialize(&mut counter);
+        counter.0
+    }
+
+    /// Serializes the instance directly into the given writer, without first buffering the
+    /// whole serialized object in memory.
+    ///
+    /// Bytes are written in small chunks, so it is recommended to use a buffered writer if
+    /// the underlying writer is expensive to call. Serialization stops at the first error
+    /// returned by the writer, and said error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grammers_tl_types::{types, Serializable};
+    ///
+    /// let photo = types::InputPhoto {
+    ///     id: 1,
+    ///     access_hash: 2,
+    ///     file_reference: vec![3; 1000],
+    /// };
+    ///
+    /// let mut written = Vec::new();
+    /// photo.serialize_to_writer(&mut written).unwrap();
+    /// assert_eq!(written, photo.to_bytes());
+    /// ```
+    fn serialize_to_writer<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        struct Adapter<'w, W> {
+            writer: &'w mut W,
+            chunk: [u8; 512],
+            len: usize,
+            result: io::Result<()>,
+        }
+
+        impl<W: Write> Adapter<'_, W> {
+            fn flush(&mut self) {
+                if self.result.is_ok() {
+                    self.result = self.writer.write_all(&self.chunk[..self.len]);
+                }
+                self.len = 0;
+            }
+        }
+
+        impl<W: Write> Extend<u8> for Adapter<'_, W> {
+            fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+                for byte in iter {
+                    if self.result.is_err() {
+                        return;
+                    }
+                    if self.len == self.chunk.len() {
+                        self.flush();
+                    }
+                    self.chunk[self.len] = byte;
+                    self.len += 1;
+                }
+            }
+        }
+
+        let mut adapter = Adapter {
+            writer,
+            chunk: [0; 512],
+            len: 0,
+            result: Ok(()),
+        };
+        self.serialize(&mut adapter);
+        adapter.flush();
+        adapter.result
+    }
 }
 
 impl Serializable for bool {
@@ -42,6 +156,10 @@ impl Serializable for bool {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         if *self { 0x997275b5u32 } else { 0xbc799737u32 }.serialize(buf)
     }
+
+    fn serialized_len(&self) -> usize {
+        4
+    }
 }
 
 impl Serializable for i32 {
@@ -64,6 +182,10 @@ impl Serializable for i32 {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         buf.extend(self.to_le_bytes().iter().copied())
     }
+
+    fn serialized_len(&self) -> usize {
+        4
+    }
 }
 
 impl Serializable for u32 {
@@ -85,6 +207,10 @@ impl Serializable for u32 {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         buf.extend(self.to_le_bytes().iter().copied())
     }
+
+    fn serialized_len(&self) -> usize {
+        4
+    }
 }
 
 impl Serializable for i64 {
@@ -107,6 +233,10 @@ impl Serializable for i64 {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         buf.extend(self.to_le_bytes().iter().copied())
     }
+
+    fn serialized_len(&self) -> usize {
+        8
+    }
 }
 
 impl Serializable for [u8; 16] {
@@ -126,6 +256,10 @@ impl Serializable for [u8; 16] {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         buf.extend(self.iter().copied())
     }
+
+    fn serialized_len(&self) -> usize {
+        16
+    }
 }
 
 impl Serializable for [u8; 32] {
@@ -146,6 +280,10 @@ impl Serializable for [u8; 32] {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         buf.extend(self.iter().copied())
     }
+
+    fn serialized_len(&self) -> usize {
+        32
+    }
 }
 
 impl Serializable for f64 {
@@ -169,6 +307,10 @@ impl Serializable for f64 {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         buf.extend(self.to_le_bytes().iter().copied())
     }
+
+    fn serialized_len(&self) -> usize {
+        8
+    }
 }
 
 impl<T: Serializable> Serializable for Vec<T> {
@@ -192,6 +334,10 @@ impl<T: Serializable> Serializable for Vec<T> {
         (self.len() as i32).serialize(buf);
         self.iter().for_each(|x| x.serialize(buf));
     }
+
+    fn serialized_len(&self) -> usize {
+        8 + self.iter().map(Serializable::serialized_len).sum::<usize>()
+    }
 }
 
 impl<T: Serializable> Serializable for crate::RawVec<T> {
@@ -212,6 +358,14 @@ impl<T: Serializable> Serializable for crate::RawVec<T> {
         (self.0.len() as i32).serialize(buf);
         self.0.iter().for_each(|x| x.serialize(buf));
     }
+
+    fn serialized_len(&self) -> usize {
+        4 + self
+            .0
+            .iter()
+            .map(Serializable::serialized_len)
+            .sum::<usize>()
+    }
 }
 
 impl Serializable for String {
@@ -234,6 +388,7 @@ impl Serializable for String {
     ///    };
     ///
     ///    assert_eq!(bytes, expected);
+    ///    assert_eq!(string.to_owned().serialized_len(), expected.len());
     /// }
     ///
     /// test_string("", &[0x00], &[0x00, 0x00, 0x00]);
@@ -254,6 +409,10 @@ impl Serializable for String {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         self.as_bytes().serialize(buf)
     }
+
+    fn serialized_len(&self) -> usize {
+        self.as_bytes().serialized_len()
+    }
 }
 
 impl Serializable for Vec<u8> {
@@ -273,6 +432,10 @@ impl Serializable for Vec<u8> {
     fn serialize(&self, buf: &mut impl Extend<u8>) {
         (&self[..]).serialize(buf)
     }
+
+    fn serialized_len(&self) -> usize {
+        (&self[..]).serialized_len()
+    }
 }
 
 impl Serializable for &[u8] {
@@ -308,4 +471,9 @@ impl Serializable for &[u8] {
         buf.extend(self.iter().copied());
         buf.extend((0..padding).map(|_| 0));
     }
+
+    fn serialized_len(&self) -> usize {
+        let header = if self.len() <= 253 { 1 } else { 4 };
+        (header + self.len()).div_ceil(4) * 4
+    }
 }
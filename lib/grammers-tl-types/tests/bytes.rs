@@ -0,0 +1,34 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![cfg(feature = "tl-api")]
+use grammers_tl_types::{self as tl, Deserializable, Serializable};
+
+#[test]
+fn non_utf8_bytes_round_trip() {
+    let data = vec![0xff, 0xfe, 0x00, 0xc3, 0x28, 0x80];
+    assert!(String::from_utf8(data.clone()).is_err());
+
+    let button = tl::types::KeyboardButtonCallback {
+        requires_password: false,
+        text: "click".into(),
+        data: data.clone(),
+    };
+
+    let bytes = button.to_bytes();
+    let button = tl::types::KeyboardButtonCallback::from_bytes(&bytes).unwrap();
+    assert_eq!(button.data, data);
+    assert_eq!(button.to_bytes(), bytes);
+}
+
+#[test]
+fn long_bytes_round_trip() {
+    let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+    let bytes = data.to_bytes();
+    assert_eq!(bytes.len() % 4, 0);
+    assert_eq!(Vec::<u8>::from_bytes(&bytes).unwrap(), data);
+}
@@ -0,0 +1,23 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![cfg(all(feature = "impl-hash-eq", feature = "tl-api"))]
+use grammers_tl_types as tl;
+use std::collections::HashSet;
+
+#[test]
+fn input_peers_can_be_deduplicated() {
+    let channel = tl::enums::InputPeer::Channel(tl::types::InputPeerChannel {
+        channel_id: 123,
+        access_hash: -456,
+    });
+
+    let peers = [channel.clone(), tl::enums::InputPeer::PeerSelf, channel]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(peers.len(), 2);
+}
@@ -0,0 +1,29 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![cfg(all(feature = "id-for-name", feature = "tl-api"))]
+use grammers_tl_types::{self as tl, id_for_name, name_for_id, Identifiable};
+
+#[test]
+fn names_map_back_to_their_ids() {
+    for id in [
+        0x1cb5c415,
+        tl::types::InputPeerSelf::CONSTRUCTOR_ID,
+        tl::types::MessageEntityBold::CONSTRUCTOR_ID,
+        tl::functions::messages::SendMessage::CONSTRUCTOR_ID,
+        tl::functions::upload::GetFile::CONSTRUCTOR_ID,
+    ] {
+        assert_eq!(id_for_name(name_for_id(id)), Some(id));
+    }
+}
+
+#[test]
+fn unknown_names_have_no_id() {
+    assert_eq!(id_for_name(""), None);
+    assert_eq!(id_for_name("(unknown)"), None);
+    assert_eq!(id_for_name("InputPeerSelf"), None);
+}
@@ -0,0 +1,26 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![cfg(all(feature = "impl-default", feature = "tl-api"))]
+use grammers_tl_types::{functions, Identifiable, Serializable};
+
+#[test]
+fn default_serializes_empty_flags() {
+    let request = functions::account::UpdateProfile::default();
+    assert_eq!(request.first_name, None);
+
+    let mut expected = functions::account::UpdateProfile::CONSTRUCTOR_ID.to_bytes();
+    expected.extend(0u32.to_bytes());
+    assert_eq!(request.to_bytes(), expected);
+}
+
+#[test]
+fn default_sets_true_flags_to_false() {
+    let request = functions::account::InitTakeoutSession::default();
+    assert!(!request.contacts);
+    assert_eq!(request.file_max_size, None);
+}
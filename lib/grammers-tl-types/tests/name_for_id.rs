@@ -0,0 +1,28 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![cfg(feature = "tl-api")]
+use grammers_tl_types::{self as tl, name_for_id, Identifiable};
+
+#[test]
+fn known_ids_have_names() {
+    assert_eq!(name_for_id(0x1cb5c415), "vector");
+    assert_eq!(
+        name_for_id(tl::types::InputPeerSelf::CONSTRUCTOR_ID),
+        "inputPeerSelf"
+    );
+    assert_eq!(
+        name_for_id(tl::functions::messages::SendMessage::CONSTRUCTOR_ID),
+        "messages.sendMessage"
+    );
+}
+
+#[test]
+fn unknown_ids_have_no_name() {
+    assert_eq!(name_for_id(0), "(unknown)");
+    assert_eq!(name_for_id(u32::MAX), "(unknown)");
+}
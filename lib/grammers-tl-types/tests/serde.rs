@@ -0,0 +1,40 @@
+// Copyright 2020 - developers of the `grammers` project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![cfg(all(feature = "serde", feature = "tl-api"))]
+use grammers_tl_types as tl;
+
+#[test]
+fn enum_round_trips_through_json() {
+    let peer = tl::enums::InputPeer::Channel(tl::types::InputPeerChannel {
+        channel_id: 123,
+        access_hash: -456,
+    });
+
+    let json = serde_json::to_string(&peer).unwrap();
+    assert!(json.contains("inputPeerChannel"));
+    assert_eq!(
+        serde_json::from_str::<tl::enums::InputPeer>(&json).unwrap(),
+        peer
+    );
+}
+
+#[test]
+fn unset_true_flags_are_skipped() {
+    let request = tl::types::InputMediaPhoto {
+        spoiler: false,
+        id: tl::enums::InputPhoto::Empty,
+        ttl_seconds: Some(10),
+    };
+
+    let json = serde_json::to_string(&request).unwrap();
+    assert!(!json.contains("spoiler"));
+    assert_eq!(
+        serde_json::from_str::<tl::types::InputMediaPhoto>(&json).unwrap(),
+        request
+    );
+}